@@ -0,0 +1,80 @@
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use dungers_bitbuf::{BitRead, BitReader, BitWrite, BitWriter};
+
+const NUM_VALUES: usize = 4096;
+const WIDTHS: [usize; 4] = [3, 11, 32, 61];
+
+fn bench_read(c: &mut Criterion) {
+    let buf: Vec<u8> = (0..NUM_VALUES * 8).map(|i| (i * 37) as u8).collect();
+    let mut values = vec![0u64; NUM_VALUES];
+
+    let mut group = c.benchmark_group("read");
+    group.throughput(Throughput::Elements(NUM_VALUES as u64));
+    for num_bits in WIDTHS {
+        group.bench_with_input(
+            BenchmarkId::new("read_ubit64", num_bits),
+            &num_bits,
+            |b, &num_bits| {
+                b.iter(|| {
+                    let mut br = BitReader::new(black_box(&buf));
+                    for value in &mut values {
+                        *value = br.read_ubit64(num_bits).unwrap();
+                    }
+                    black_box(&values);
+                });
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("read_ubit64_into", num_bits),
+            &num_bits,
+            |b, &num_bits| {
+                b.iter(|| {
+                    let mut br = BitReader::new(black_box(&buf));
+                    br.read_ubit64_into(&mut values, num_bits).unwrap();
+                    black_box(&values);
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+fn bench_write(c: &mut Criterion) {
+    let values: Vec<u64> = (0..NUM_VALUES as u64)
+        .map(|i| i.wrapping_mul(0x9e37_79b9_7f4a_7c15))
+        .collect();
+    let mut buf = vec![0u8; NUM_VALUES * 8];
+
+    let mut group = c.benchmark_group("write");
+    group.throughput(Throughput::Elements(NUM_VALUES as u64));
+    for num_bits in WIDTHS {
+        group.bench_with_input(
+            BenchmarkId::new("write_ubit64", num_bits),
+            &num_bits,
+            |b, &num_bits| {
+                b.iter(|| {
+                    let mut bw = BitWriter::new(black_box(&mut buf));
+                    for value in &values {
+                        bw.write_ubit64(*value, num_bits).unwrap();
+                    }
+                });
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("write_ubit64_from", num_bits),
+            &num_bits,
+            |b, &num_bits| {
+                b.iter(|| {
+                    let mut bw = BitWriter::new(black_box(&mut buf));
+                    bw.write_ubit64_from(&values, num_bits).unwrap();
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_read, bench_write);
+criterion_main!(benches);
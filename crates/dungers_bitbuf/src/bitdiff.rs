@@ -0,0 +1,205 @@
+use core::fmt::{self, Write};
+
+use crate::{BitError, BitRead, BitReader};
+
+/// a range of bits within a byte buffer.
+#[derive(Debug, Clone, Copy)]
+pub struct BitSlice<'a> {
+    data: &'a [u8],
+    offset: usize,
+    num_bits: usize,
+}
+
+impl<'a> BitSlice<'a> {
+    /// all bits of `data`.
+    #[must_use]
+    pub fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            offset: 0,
+            num_bits: data.len() << 3,
+        }
+    }
+
+    /// `num_bits` bits of `data`, starting at bit `offset`.
+    pub fn with_range(data: &'a [u8], offset: usize, num_bits: usize) -> Result<Self, BitError> {
+        let end = offset.checked_add(num_bits).ok_or(BitError::Overflow)?;
+        if end > data.len() << 3 {
+            return Err(BitError::Overflow);
+        }
+        Ok(Self {
+            data,
+            offset,
+            num_bits,
+        })
+    }
+
+    #[must_use]
+    pub fn num_bits(&self) -> usize {
+        self.num_bits
+    }
+
+    /// returns a reader positioned at bit `bit` of the slice.
+    fn reader_at(&self, bit: usize) -> BitReader<'a> {
+        BitReader::new_at(self.data, self.offset + bit)
+    }
+
+    fn get(&self, bit: usize) -> Option<bool> {
+        (bit < self.num_bits).then(|| self.reader_at(bit).read_bool().unwrap_or_default())
+    }
+}
+
+impl<'a> From<&'a [u8]> for BitSlice<'a> {
+    fn from(data: &'a [u8]) -> Self {
+        Self::new(data)
+    }
+}
+
+impl<'a, const N: usize> From<&'a [u8; N]> for BitSlice<'a> {
+    fn from(data: &'a [u8; N]) -> Self {
+        Self::new(data)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&'a alloc::vec::Vec<u8>> for BitSlice<'a> {
+    fn from(data: &'a alloc::vec::Vec<u8>) -> Self {
+        Self::new(data)
+    }
+}
+
+/// the first difference between two bit slices; see [`diff_bits`].
+///
+/// its [`fmt::Display`] impl renders both slices side by side around the difference, marking
+/// differing bits. bits are printed in the order they would be read, grouped into bytes.
+#[derive(Debug, Clone, Copy)]
+pub struct BitDiff<'a> {
+    left: BitSlice<'a>,
+    right: BitSlice<'a>,
+    bit: usize,
+}
+
+impl BitDiff<'_> {
+    /// position of the first differing bit, relative to the start of each slice. if one slice is
+    /// a prefix of the other this is the length of the shorter one.
+    #[must_use]
+    pub fn bit(&self) -> usize {
+        self.bit
+    }
+}
+
+/// compares two bit slices and returns the first difference, or [`None`] if they are equal
+/// (which includes having the same length).
+pub fn diff_bits<'a>(
+    left: impl Into<BitSlice<'a>>,
+    right: impl Into<BitSlice<'a>>,
+) -> Option<BitDiff<'a>> {
+    let left = left.into();
+    let right = right.into();
+
+    let num_bits = left.num_bits.min(right.num_bits);
+    let mut lr = left.reader_at(0);
+    let mut rr = right.reader_at(0);
+
+    let mut bit = 0;
+    while bit < num_bits {
+        let n = (num_bits - bit).min(64);
+        let l = lr.read_ubit64(n).unwrap_or_default();
+        let r = rr.read_ubit64(n).unwrap_or_default();
+        if l != r {
+            let bit = bit + (l ^ r).trailing_zeros() as usize;
+            return Some(BitDiff { left, right, bit });
+        }
+        bit += n;
+    }
+
+    (left.num_bits != right.num_bits).then_some(BitDiff { left, right, bit })
+}
+
+const ROW_BITS: usize = 32;
+// 32 bits + a space between every byte.
+const ROW_WIDTH: usize = ROW_BITS + ROW_BITS / 8 - 1;
+
+/// writes `num_cells` cells, with a space between every 8.
+fn write_cells(
+    f: &mut fmt::Formatter<'_>,
+    num_cells: usize,
+    cell: impl Fn(usize) -> char,
+) -> fmt::Result {
+    for i in 0..num_cells {
+        if i > 0 && i % 8 == 0 {
+            f.write_char(' ')?;
+        }
+        f.write_char(cell(i))?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for BitDiff<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "bits differ at {} (left has {} bits, right has {} bits)",
+            self.bit, self.left.num_bits, self.right.num_bits,
+        )?;
+        write!(f, "{:>8}  {:<ROW_WIDTH$}  right", "bit", "left")?;
+
+        // one row of context before and after the row that contains the difference.
+        let diff_row = self.bit / ROW_BITS;
+        let num_rows = self
+            .left
+            .num_bits
+            .max(self.right.num_bits)
+            .div_ceil(ROW_BITS);
+        let first_row = diff_row.saturating_sub(1);
+        let last_row = (diff_row + 1).min(num_rows.saturating_sub(1)).max(diff_row);
+
+        for row in first_row..=last_row {
+            let row_bit = row * ROW_BITS;
+            let bit_cell = |bit: Option<bool>| match bit {
+                Some(true) => '1',
+                Some(false) => '0',
+                None => ' ',
+            };
+            write!(f, "\n{row_bit:>8}  ")?;
+            write_cells(f, ROW_BITS, |i| bit_cell(self.left.get(row_bit + i)))?;
+            f.write_str("  ")?;
+            write_cells(f, ROW_BITS, |i| bit_cell(self.right.get(row_bit + i)))?;
+
+            // markers go up to the last differing bit of the row, under both columns.
+            let differs = |i: usize| self.left.get(row_bit + i) != self.right.get(row_bit + i);
+            if let Some(last) = (0..ROW_BITS).rev().find(|&i| differs(i)) {
+                let marker_cell = |i| if differs(i) { '^' } else { ' ' };
+                let marker_width = last + 1 + last / 8;
+                write!(f, "\n{:>8}  ", "")?;
+                write_cells(f, last + 1, marker_cell)?;
+                write!(f, "{:pad$}  ", "", pad = ROW_WIDTH - marker_width)?;
+                write_cells(f, last + 1, marker_cell)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// asserts that two bit slices are equal. on failure the panic message contains a bit-level diff
+/// around the first difference; see [`BitDiff`].
+///
+/// both arguments can be anything that converts into a [`BitSlice`]: byte slices, arrays, vecs,
+/// or a [`BitSlice`] itself to compare a sub-range.
+#[macro_export]
+macro_rules! assert_bits_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        if let Some(diff) = $crate::diff_bits($left, $right) {
+            panic!("assertion `left == right` failed\n{diff}");
+        }
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        if let Some(diff) = $crate::diff_bits($left, $right) {
+            panic!(
+                "assertion `left == right` failed: {}\n{diff}",
+                format_args!($($arg)+)
+            );
+        }
+    };
+}
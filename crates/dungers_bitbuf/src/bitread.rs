@@ -0,0 +1,472 @@
+#[cfg(feature = "varint")]
+use core::marker::PhantomData;
+
+#[cfg(feature = "varint")]
+use dungers_varint::{
+    CONTINUE_BIT, MAX_VARINT_LEN, Sleb128, Varint,
+    wire::{Tag, WireType},
+};
+
+use crate::BitError;
+
+/// a source of bits.
+///
+/// implementors only need to provide the required core (position tracking, [`Self::seek`] and
+/// [`Self::read_ubit64`]); everything else is built on top of it and can be overridden where a
+/// faster implementation is possible.
+pub trait BitRead {
+    #[must_use]
+    fn num_bits_left(&self) -> usize;
+
+    #[must_use]
+    fn num_bits_read(&self) -> usize;
+
+    /// seek to a specific bit.
+    fn seek(&mut self, bit: usize) -> Result<(), BitError>;
+
+    /// `read_ubit64` reads the specified number of bits into a `u64`. the function can read up to a
+    /// maximum of 64 bits at a time. if the `num_bits` exceeds the number of remaining bits, the
+    /// function returns an [`BitError::Overflow`] error.
+    fn read_ubit64(&mut self, num_bits: usize) -> Result<u64, BitError>;
+
+    #[must_use]
+    fn num_bytes_left(&self) -> usize {
+        self.num_bits_left() >> 3
+    }
+
+    #[must_use]
+    fn num_bytes_read(&self) -> usize {
+        (self.num_bits_read() + 7) >> 3
+    }
+
+    /// seek to an offset from the current position.
+    fn seek_relative(&mut self, bit_delta: isize) -> Result<usize, BitError> {
+        let bit = isize::try_from(self.num_bits_read())? + bit_delta;
+        self.seek(bit.try_into()?)?;
+        Ok(self.num_bits_read())
+    }
+
+    /// skip the specified number of bits.
+    fn skip_bits(&mut self, num_bits: usize) -> Result<usize, BitError> {
+        let bit = self
+            .num_bits_read()
+            .checked_add(num_bits)
+            .ok_or(BitError::Overflow)?;
+        self.seek(bit)?;
+        Ok(bit)
+    }
+
+    /// skip to the next byte boundary. does nothing if already byte-aligned.
+    fn align_to_byte(&mut self) -> Result<usize, BitError> {
+        let bit = self.num_bits_read().next_multiple_of(8);
+        self.seek(bit)?;
+        Ok(bit)
+    }
+
+    #[must_use]
+    fn is_byte_aligned(&self) -> bool {
+        self.num_bits_read().is_multiple_of(8)
+    }
+
+    fn read_bool(&mut self) -> Result<bool, BitError> {
+        self.read_ubit64(1).map(|bit| bit == 1)
+    }
+
+    fn read_byte(&mut self) -> Result<u8, BitError> {
+        self.read_ubit64(8)
+            .and_then(|b| b.try_into().map_err(BitError::TryFromIntError))
+    }
+
+    fn read_bits(&mut self, buf: &mut [u8], num_bits: usize) -> Result<(), BitError> {
+        if buf.len() << 3 < num_bits || self.num_bits_left() < num_bits {
+            return Err(BitError::Overflow);
+        }
+
+        let mut bits_left = num_bits;
+        let mut bytes_written = 0;
+
+        while bits_left >= 64 {
+            let value = self.read_ubit64(64)?;
+            let bytes = value.to_le_bytes();
+
+            let dest_range = bytes_written..bytes_written + 8;
+            buf[dest_range].copy_from_slice(&bytes);
+
+            bytes_written += 8;
+            bits_left -= 64;
+        }
+
+        while bits_left >= 8 {
+            buf[bytes_written] = self.read_ubit64(8)?.try_into()?;
+            bytes_written += 1;
+            bits_left -= 8;
+        }
+
+        if bits_left > 0 {
+            buf[bytes_written] = self.read_ubit64(bits_left)?.try_into()?;
+        }
+
+        Ok(())
+    }
+
+    fn read_bytes(&mut self, buf: &mut [u8]) -> Result<(), BitError> {
+        self.read_bits(buf, buf.len() << 3)
+    }
+
+    /// reads `buf.len()` values of `num_bits` bits each. bounds are checked once, up front; if
+    /// there are not enough bits left nothing is read.
+    fn read_ubit64_into(&mut self, buf: &mut [u64], num_bits: usize) -> Result<(), BitError> {
+        let total_bits = buf.len().checked_mul(num_bits).ok_or(BitError::Overflow)?;
+        if num_bits > 64 || self.num_bits_left() < total_bits {
+            return Err(BitError::Overflow);
+        }
+
+        for dst in buf {
+            *dst = self.read_ubit64(num_bits)?;
+        }
+
+        Ok(())
+    }
+
+    /// same as [`Self::read_ubit64_into`], but for values of up to 32 bits.
+    fn read_u32s_into(&mut self, buf: &mut [u32], num_bits: usize) -> Result<(), BitError> {
+        let total_bits = buf.len().checked_mul(num_bits).ok_or(BitError::Overflow)?;
+        if num_bits > 32 || self.num_bits_left() < total_bits {
+            return Err(BitError::Overflow);
+        }
+
+        for dst in buf {
+            *dst = self.read_ubit64(num_bits)?.try_into()?;
+        }
+
+        Ok(())
+    }
+
+    fn read_u16(&mut self) -> Result<u16, BitError> {
+        Ok(self.read_ubit64(16)?.try_into()?)
+    }
+
+    fn read_u32(&mut self) -> Result<u32, BitError> {
+        Ok(self.read_ubit64(32)?.try_into()?)
+    }
+
+    fn read_u64(&mut self) -> Result<u64, BitError> {
+        self.read_ubit64(64)
+    }
+
+    fn read_i8(&mut self) -> Result<i8, BitError> {
+        self.read_byte().map(u8::cast_signed)
+    }
+
+    fn read_i16(&mut self) -> Result<i16, BitError> {
+        self.read_u16().map(u16::cast_signed)
+    }
+
+    fn read_i32(&mut self) -> Result<i32, BitError> {
+        self.read_u32().map(u32::cast_signed)
+    }
+
+    fn read_i64(&mut self) -> Result<i64, BitError> {
+        self.read_u64().map(u64::cast_signed)
+    }
+
+    fn read_f32(&mut self) -> Result<f32, BitError> {
+        self.read_u32().map(f32::from_bits)
+    }
+
+    fn read_f64(&mut self) -> Result<f64, BitError> {
+        self.read_u64().map(f64::from_bits)
+    }
+
+    /// reads a null-terminated string into `buf` and returns its length, not including the null
+    /// terminator.
+    ///
+    /// if `buf` is too small the string is still consumed up to and including the null
+    /// terminator, but [`BitError::BufferTooSmall`] is returned.
+    fn read_string(&mut self, buf: &mut [u8]) -> Result<usize, BitError> {
+        let mut len = 0;
+        let mut too_small = false;
+        loop {
+            let byte = self.read_byte()?;
+            if byte == 0 {
+                break;
+            }
+            match buf.get_mut(len) {
+                Some(dst) => {
+                    *dst = byte;
+                    len += 1;
+                }
+                None => too_small = true,
+            }
+        }
+
+        if too_small {
+            Err(BitError::BufferTooSmall)
+        } else {
+            Ok(len)
+        }
+    }
+
+    /// reads a varint encoded `T`; signed types are zigzag decoded. see [`Varint`].
+    #[cfg(feature = "varint")]
+    fn read_varint<T: Varint>(&mut self) -> Result<T, BitError> {
+        self.read_varint_with_len().map(|(value, _)| value)
+    }
+
+    /// like [`Self::read_varint`], but also returns the number of bytes the varint occupied, the
+    /// way the [`dungers_varint`] io functions do.
+    #[cfg(feature = "varint")]
+    fn read_varint_with_len<T: Varint>(&mut self) -> Result<(T, usize), BitError> {
+        let (buf, n) = read_varint_bytes(self, T::MAX_LEN)?;
+        Ok(T::decode(&buf[..n])?)
+    }
+
+    /// like [`Self::read_varint`], but only accepts the canonical encoding: returns
+    /// [`BitError::NonCanonicalVarint`] for overlong encodings and [`BitError::VarintOverflow`] if
+    /// the last byte carries bits that do not fit into `T`.
+    #[cfg(feature = "varint")]
+    fn read_varint_strict<T: Varint>(&mut self) -> Result<T, BitError> {
+        self.read_varint_strict_with_len().map(|(value, _)| value)
+    }
+
+    /// see [`Self::read_varint_strict`] and [`Self::read_varint_with_len`].
+    #[cfg(feature = "varint")]
+    fn read_varint_strict_with_len<T: Varint>(&mut self) -> Result<(T, usize), BitError> {
+        let (buf, n) = read_varint_bytes(self, T::MAX_LEN)?;
+        Ok(T::decode_strict(&buf[..n])?)
+    }
+
+    /// reads a plain LEB128 varint into `T`. unlike [`Self::read_varint`], signed types are not
+    /// zigzag decoded: the unsigned value's bits are reinterpreted as `T`.
+    #[cfg(feature = "varint")]
+    fn read_uvarint<T: Varint>(&mut self) -> Result<T, BitError> {
+        self.read_varint::<T::Unsigned>().map(T::from_unsigned)
+    }
+
+    /// see [`Self::read_uvarint`] and [`Self::read_varint_strict`].
+    #[cfg(feature = "varint")]
+    fn read_uvarint_strict<T: Varint>(&mut self) -> Result<T, BitError> {
+        self.read_varint_strict::<T::Unsigned>()
+            .map(T::from_unsigned)
+    }
+
+    #[cfg(feature = "varint")]
+    fn read_uvarint64(&mut self) -> Result<u64, BitError> {
+        self.read_varint()
+    }
+
+    #[cfg(feature = "varint")]
+    fn read_varint64(&mut self) -> Result<i64, BitError> {
+        self.read_varint()
+    }
+
+    #[cfg(feature = "varint")]
+    fn read_uvarint32(&mut self) -> Result<u32, BitError> {
+        self.read_varint()
+    }
+
+    #[cfg(feature = "varint")]
+    fn read_varint32(&mut self) -> Result<i32, BitError> {
+        self.read_varint()
+    }
+
+    #[cfg(feature = "varint")]
+    fn read_uvarint64_strict(&mut self) -> Result<u64, BitError> {
+        self.read_varint_strict()
+    }
+
+    #[cfg(feature = "varint")]
+    fn read_varint64_strict(&mut self) -> Result<i64, BitError> {
+        self.read_varint_strict()
+    }
+
+    #[cfg(feature = "varint")]
+    fn read_uvarint32_strict(&mut self) -> Result<u32, BitError> {
+        self.read_varint_strict()
+    }
+
+    #[cfg(feature = "varint")]
+    fn read_varint32_strict(&mut self) -> Result<i32, BitError> {
+        self.read_varint_strict()
+    }
+
+    /// see [`Self::read_varint_with_len`].
+    #[cfg(feature = "varint")]
+    fn read_uvarint64_with_len(&mut self) -> Result<(u64, usize), BitError> {
+        self.read_varint_with_len()
+    }
+
+    #[cfg(feature = "varint")]
+    fn read_varint64_with_len(&mut self) -> Result<(i64, usize), BitError> {
+        self.read_varint_with_len()
+    }
+
+    #[cfg(feature = "varint")]
+    fn read_uvarint32_with_len(&mut self) -> Result<(u32, usize), BitError> {
+        self.read_varint_with_len()
+    }
+
+    #[cfg(feature = "varint")]
+    fn read_varint32_with_len(&mut self) -> Result<(i32, usize), BitError> {
+        self.read_varint_with_len()
+    }
+
+    /// reads a signed LEB128 encoded `T`; see [`Sleb128`].
+    #[cfg(feature = "varint")]
+    fn read_sleb128<T: Sleb128>(&mut self) -> Result<T, BitError> {
+        let (buf, n) = read_varint_bytes(self, T::MAX_LEN)?;
+        Ok(T::decode_sleb128(&buf[..n])?.0)
+    }
+
+    /// reads a protobuf field tag; see [`dungers_varint::wire`]. fixed32/fixed64 values are read
+    /// with [`Self::read_u32`]/[`Self::read_u64`].
+    #[cfg(feature = "varint")]
+    fn read_tag(&mut self) -> Result<Tag, BitError> {
+        let value = self.read_varint::<u64>()?;
+        Ok(Tag::from_u32(
+            u32::try_from(value).map_err(|_| BitError::InvalidTag)?,
+        )?)
+    }
+
+    /// skips the value of a protobuf field whose tag has just been read; see
+    /// [`dungers_varint::wire::WireReader::skip_field`].
+    #[cfg(feature = "varint")]
+    fn skip_field(&mut self, tag: Tag) -> Result<(), BitError> {
+        match tag.wire_type {
+            WireType::Varint => self.read_varint::<u64>().map(drop),
+            WireType::Fixed64 => self.skip_bits(64).map(drop),
+            WireType::LengthDelimited => {
+                let len = read_wire_len(self)?;
+                self.skip_bits(len.checked_mul(8).ok_or(BitError::Overflow)?)
+                    .map(drop)
+            }
+            WireType::Fixed32 => self.skip_bits(32).map(drop),
+            WireType::StartGroup => {
+                // groups nest; track the depth instead of recursing.
+                let mut depth = 1usize;
+                while depth > 0 {
+                    let inner = self.read_tag()?;
+                    match inner.wire_type {
+                        WireType::StartGroup => depth += 1,
+                        WireType::EndGroup => depth -= 1,
+                        _ => self.skip_field(inner)?,
+                    }
+                }
+                Ok(())
+            }
+            WireType::EndGroup => Err(BitError::InvalidTag),
+        }
+    }
+
+    /// reads a protobuf packed repeated field of varints: a varint length followed by that many
+    /// bytes of varints; see [`dungers_varint::wire::WireReader::read_packed_varints`]. the
+    /// values are decoded as the iterator is advanced, so it needs to be exhausted to move past
+    /// the field.
+    #[cfg(feature = "varint")]
+    fn read_packed_varints<T: Varint>(&mut self) -> Result<PackedVarints<'_, Self, T>, BitError> {
+        let len = read_wire_len(self)?;
+        let num_bits = len.checked_mul(8).ok_or(BitError::Overflow)?;
+        if num_bits > self.num_bits_left() {
+            return Err(BitError::Overflow);
+        }
+        Ok(PackedVarints {
+            end: self.num_bits_read() + num_bits,
+            reader: self,
+            _marker: PhantomData,
+        })
+    }
+}
+
+/// iterator over a packed repeated field of varints; see [`BitRead::read_packed_varints`].
+#[cfg(feature = "varint")]
+#[derive(Debug)]
+pub struct PackedVarints<'a, R: ?Sized, T> {
+    reader: &'a mut R,
+    /// the bit the field ends at.
+    end: usize,
+    _marker: PhantomData<T>,
+}
+
+#[cfg(feature = "varint")]
+impl<R: BitRead + ?Sized, T: Varint> Iterator for PackedVarints<'_, R, T> {
+    type Item = Result<T, BitError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.reader.num_bits_read() >= self.end {
+            return None;
+        }
+        let result = self.reader.read_varint().and_then(|value| {
+            // a varint must not run past the end of the field.
+            if self.reader.num_bits_read() > self.end {
+                Err(BitError::MalformedVarint)
+            } else {
+                Ok(value)
+            }
+        });
+        if result.is_err() {
+            // stop after the first error.
+            self.end = 0;
+        }
+        Some(result)
+    }
+}
+
+/// reads the length of a protobuf length-delimited value. lengths are 32-bit; a longer varint is
+/// rejected rather than truncated.
+#[cfg(feature = "varint")]
+pub(crate) fn read_wire_len<R: BitRead + ?Sized>(r: &mut R) -> Result<usize, BitError> {
+    let len = u32::try_from(r.read_varint::<u64>()?).map_err(|_| BitError::VarintOverflow)?;
+    Ok(len.try_into()?)
+}
+
+/// reads bytes up to and including the one without a continuation bit, but no more than
+/// `max_len`.
+#[cfg(feature = "varint")]
+fn read_varint_bytes<R: BitRead + ?Sized>(
+    r: &mut R,
+    max_len: usize,
+) -> Result<([u8; MAX_VARINT_LEN], usize), BitError> {
+    let mut buf = [0u8; MAX_VARINT_LEN];
+    for count in 0..max_len {
+        buf[count] = r.read_byte()?;
+        if (buf[count] & CONTINUE_BIT) == 0 {
+            return Ok((buf, count + 1));
+        }
+    }
+
+    Err(BitError::MalformedVarint)
+}
+
+impl<R: BitRead> BitRead for &mut R {
+    fn num_bits_left(&self) -> usize {
+        (**self).num_bits_left()
+    }
+
+    fn num_bits_read(&self) -> usize {
+        (**self).num_bits_read()
+    }
+
+    fn seek(&mut self, bit: usize) -> Result<(), BitError> {
+        (**self).seek(bit)
+    }
+
+    fn read_ubit64(&mut self, num_bits: usize) -> Result<u64, BitError> {
+        (**self).read_ubit64(num_bits)
+    }
+
+    fn read_bool(&mut self) -> Result<bool, BitError> {
+        (**self).read_bool()
+    }
+
+    fn read_bits(&mut self, buf: &mut [u8], num_bits: usize) -> Result<(), BitError> {
+        (**self).read_bits(buf, num_bits)
+    }
+
+    fn read_ubit64_into(&mut self, buf: &mut [u64], num_bits: usize) -> Result<(), BitError> {
+        (**self).read_ubit64_into(buf, num_bits)
+    }
+
+    fn read_u32s_into(&mut self, buf: &mut [u32], num_bits: usize) -> Result<(), BitError> {
+        (**self).read_u32s_into(buf, num_bits)
+    }
+}
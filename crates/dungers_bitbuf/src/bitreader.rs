@@ -1,16 +1,16 @@
-#[cfg(feature = "varint")]
-use dungers_varint::{
-    CONTINUE_BIT, PAYLOAD_BITS, max_varint_size, zigzag_decode32, zigzag_decode64,
-};
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, vec};
 
-use crate::{BitError, EXTRA_MASKS};
+#[cfg(all(feature = "varint", feature = "alloc"))]
+use crate::bitread::read_wire_len;
+use crate::{BitError, BitRead, EXTRA_MASKS, load_block};
 
 // NOTE(blukai): introduction of "caching" didn't yeild any performance inprovements, in fact quite
 // the opposite happened. numbers were degraded.
 
 pub struct BitReader<'a> {
     num_bits: usize,
-    data: &'a [u64],
+    data: &'a [u8],
     cur_bit: usize,
 }
 
@@ -19,70 +19,213 @@ impl<'a> BitReader<'a> {
     pub fn new(data: &'a [u8]) -> Self {
         Self {
             num_bits: data.len() << 3,
-            #[allow(unsafe_code)]
-            data: unsafe {
-                // SAFETY: it is okay to transmute u8s into u64s here, even if slice of slice does
-                // not contain enough (8 / size_of::<u64>()).
-                //
-                // that is because all "safe" methods carefully keep track of where the reading is
-                // taking place and any out of bound read will result in an error.
-                //
-                // BUT! "unsafe" `unchecked` methods may allow out of bounds reads - that is ub. in
-                // debug builds assertions will yell at you loudly if something is not right, but
-                // those assertions will not be present in release builds.
-                std::mem::transmute::<&[u8], &[u64]>(data)
-            },
+            data,
             cur_bit: 0,
         }
     }
 
-    #[must_use]
-    pub fn num_bits_left(&self) -> usize {
-        self.num_bits - self.cur_bit
+    /// creates a reader over `data` positioned at `cur_bit`.
+    pub(crate) fn new_at(data: &'a [u8], cur_bit: usize) -> Self {
+        debug_assert!(cur_bit <= data.len() << 3);
+        Self {
+            num_bits: data.len() << 3,
+            data,
+            cur_bit,
+        }
     }
 
-    #[must_use]
-    pub fn num_bytes_left(&self) -> usize {
-        self.num_bits_left() >> 3
+    /// reads `len` bytes by borrowing them directly from the input, without copying.
+    ///
+    /// returns [`BitError::Unaligned`] if the current bit is not byte-aligned.
+    #[cfg_attr(
+        feature = "alloc",
+        doc = "see [`Self::read_slice`] for a variant that falls back to copying."
+    )]
+    pub fn read_aligned_slice(&mut self, len: usize) -> Result<&'a [u8], BitError> {
+        if !self.is_byte_aligned() {
+            return Err(BitError::Unaligned);
+        }
+        if self.num_bytes_left() < len {
+            return Err(BitError::Overflow);
+        }
+
+        let start = self.cur_bit >> 3;
+        let slice = self
+            .data
+            .get(start..start + len)
+            .ok_or(BitError::Overflow)?;
+        self.cur_bit += len << 3;
+        Ok(slice)
     }
 
-    #[must_use]
-    pub fn num_bits_read(&self) -> usize {
-        self.cur_bit
+    /// reads `len` bytes. borrows from the input when the current bit is byte-aligned, otherwise
+    /// copies.
+    #[cfg(feature = "alloc")]
+    pub fn read_slice(&mut self, len: usize) -> Result<Cow<'a, [u8]>, BitError> {
+        if self.is_byte_aligned() {
+            return self.read_aligned_slice(len).map(Cow::Borrowed);
+        }
+        // check before allocating; `len` may come straight off the wire.
+        if self.num_bytes_left() < len {
+            return Err(BitError::Overflow);
+        }
+
+        let mut buf = vec![0u8; len];
+        self.read_bytes(&mut buf)?;
+        Ok(Cow::Owned(buf))
     }
 
-    #[must_use]
-    pub fn num_bytes_read(&self) -> usize {
-        (self.cur_bit + 7) >> 3
+    /// reads a protobuf length-delimited value: a varint length followed by that many bytes. see
+    /// [`Self::read_slice`]; the length is checked against the remaining input before anything is
+    /// allocated.
+    #[cfg(all(feature = "varint", feature = "alloc"))]
+    pub fn read_length_delimited(&mut self) -> Result<Cow<'a, [u8]>, BitError> {
+        let len = read_wire_len(self)?;
+        self.read_slice(len)
     }
 
-    /// seek to a specific bit.
-    pub fn seek(&mut self, bit: usize) -> Result<(), BitError> {
-        if bit > self.num_bits {
+    /// searches forward from the current bit for the next position where
+    /// [`BitRead::read_ubit64`]`(width)` would return `pattern` (only the lowest `width` bits of
+    /// `pattern` are compared). returns the bit offset of the match; does not move the cursor.
+    pub fn find_pattern(&self, pattern: u64, width: usize) -> Result<Option<usize>, BitError> {
+        self.find_any_pattern(&[(pattern, width)])
+            .map(|found| found.map(|(_, bit)| bit))
+    }
+
+    /// same as [`Self::find_pattern`], but searches for several `(pattern, width)` pairs at once.
+    /// returns the index of the pattern and the bit offset of the earliest match; if multiple
+    /// patterns match at the same offset the first one wins.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn find_any_pattern(
+        &self,
+        patterns: &[(u64, usize)],
+    ) -> Result<Option<(usize, usize)>, BitError> {
+        if patterns.iter().any(|&(_, width)| width > 64) {
             return Err(BitError::Overflow);
         }
-        self.cur_bit = bit;
+
+        let mut block_idx = self.cur_bit >> 6;
+        let mut shift = self.cur_bit & 63;
+
+        // a pair of blocks holds every window that starts within the first one; shift through
+        // them instead of reading bit by bit.
+        while block_idx << 6 <= self.num_bits {
+            let lo = load_block(self.data, block_idx).unwrap_or_default();
+            let hi = load_block(self.data, block_idx + 1).unwrap_or_default();
+            let blocks = u128::from(hi) << 64 | u128::from(lo);
+
+            for s in shift..64 {
+                let bit = (block_idx << 6) + s;
+                let window = (blocks >> s) as u64;
+                for (i, &(pattern, width)) in patterns.iter().enumerate() {
+                    if bit + width <= self.num_bits && (window ^ pattern) & EXTRA_MASKS[width] == 0
+                    {
+                        return Ok(Some((i, bit)));
+                    }
+                }
+            }
+
+            block_idx += 1;
+            shift = 0;
+        }
+
+        Ok(None)
+    }
+
+    /// decodes `len` consecutive `num_bits` wide values, passing each to `put` along with its
+    /// index. the caller is responsible for checking bounds.
+    ///
+    /// instead of going through [`BitRead::read_ubit64`] for every value, this keeps the current
+    /// block around and only loads the next one when it runs out of bits.
+    #[allow(clippy::cast_possible_truncation)]
+    fn read_ubit64_blocks(
+        &mut self,
+        len: usize,
+        num_bits: usize,
+        mut put: impl FnMut(usize, u64),
+    ) -> Result<(), BitError> {
+        debug_assert!(num_bits <= 64 && len * num_bits <= self.num_bits_left());
+
+        if len == 0 {
+            return Ok(());
+        }
+        if num_bits == 0 {
+            // nothing to load; zero-width values are 0, like `read_ubit64(0)` returns.
+            (0..len).for_each(|i| put(i, 0));
+            return Ok(());
+        }
+
+        let mask = EXTRA_MASKS[num_bits];
+
+        let mut block_idx = self.cur_bit >> 6;
+        let shift = self.cur_bit & 63;
+        let mut block = load_block(self.data, block_idx).ok_or(BitError::Overflow)? >> shift;
+        // number of not yet consumed bits in `block`.
+        let mut avail = 64 - shift;
+
+        for i in 0..len {
+            if num_bits <= avail {
+                put(i, block & mask);
+                block = block.unbounded_shr(num_bits as u32);
+                avail -= num_bits;
+            } else {
+                block_idx += 1;
+                let next = load_block(self.data, block_idx).ok_or(BitError::Overflow)?;
+                put(i, (block | next.unbounded_shl(avail as u32)) & mask);
+                block = next.unbounded_shr((num_bits - avail) as u32);
+                avail = 64 - (num_bits - avail);
+            }
+        }
+
+        self.cur_bit += len * num_bits;
         Ok(())
     }
 
-    /// seek to an offset from the current position.
-    pub fn seek_relative(&mut self, bit_delta: isize) -> Result<usize, BitError> {
-        let bit = isize::try_from(self.cur_bit)? + bit_delta;
-        self.seek(bit.try_into()?)?;
-        Ok(self.cur_bit)
+    /// this can save your ass when you're using `_unchecked` methods. once you're done reading
+    /// from buf call this to see if any bits were read from kyokai no kanata.
+    ///
+    /// returns [`BitError::Overflow`] if overflowed (which means you are skrewed).
+    ///
+    /// i figured that returning result would be more convenient than a bool because it can be
+    /// questionmarked; plus, in some cases, this would eliminate a need of coming up with a custom
+    /// error.
+    pub fn is_overflowed(&self) -> Result<(), BitError> {
+        if self.cur_bit > self.num_bits {
+            Err(BitError::Overflow)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl BitRead for BitReader<'_> {
+    fn num_bits_left(&self) -> usize {
+        self.num_bits - self.cur_bit
     }
 
-    /// `read_ubit64` reads the specified number of bits into a `u64`. the function can read up to a
-    /// maximum of 64 bits at a time. if the `num_bits` exceeds the number of remaining bits, the
-    /// function returns an [`Error::Overflow`] error.
-    pub fn read_ubit64(&mut self, num_bits: usize) -> Result<u64, BitError> {
+    fn num_bits_read(&self) -> usize {
+        self.cur_bit
+    }
+
+    fn seek(&mut self, bit: usize) -> Result<(), BitError> {
+        if bit > self.num_bits {
+            return Err(BitError::Overflow);
+        }
+        self.cur_bit = bit;
+        Ok(())
+    }
+
+    fn read_ubit64(&mut self, num_bits: usize) -> Result<u64, BitError> {
         if num_bits > 64 || self.num_bits_left() < num_bits {
             return Err(BitError::Overflow);
         }
+        if num_bits == 0 {
+            return Ok(0);
+        }
 
         let block1_idx = self.cur_bit >> 6;
 
-        let mut block1 = *self.data.get(block1_idx).ok_or(BitError::Overflow)?;
+        let mut block1 = load_block(self.data, block1_idx).ok_or(BitError::Overflow)?;
         // get the bits we're interested in
         block1 >>= self.cur_bit & 63;
 
@@ -95,7 +238,7 @@ impl<'a> BitReader<'a> {
         } else {
             let extra_bits = self.cur_bit & 63;
 
-            let mut block2 = *self.data.get(block1_idx + 1).ok_or(BitError::Overflow)?;
+            let mut block2 = load_block(self.data, block1_idx + 1).ok_or(BitError::Overflow)?;
             block2 &= EXTRA_MASKS[extra_bits];
 
             // no need to mask since we hit the end of the block. shift the second block's part
@@ -106,23 +249,18 @@ impl<'a> BitReader<'a> {
         Ok(ret)
     }
 
-    pub fn read_bool(&mut self) -> Result<bool, BitError> {
+    fn read_bool(&mut self) -> Result<bool, BitError> {
         if self.num_bits_left() < 1 {
             return Err(BitError::Overflow);
         }
 
-        let block1 = *self.data.get(self.cur_bit >> 6).ok_or(BitError::Overflow)?;
+        let block1 = load_block(self.data, self.cur_bit >> 6).ok_or(BitError::Overflow)?;
         let one_bit = block1 >> (self.cur_bit & 63) & 1;
         self.cur_bit += 1;
         Ok(one_bit == 1)
     }
 
-    pub fn read_byte(&mut self) -> Result<u8, BitError> {
-        self.read_ubit64(8)
-            .and_then(|b| b.try_into().map_err(BitError::TryFromIntError))
-    }
-
-    pub fn read_bits(&mut self, buf: &mut [u8], num_bits: usize) -> Result<(), BitError> {
+    fn read_bits(&mut self, buf: &mut [u8], num_bits: usize) -> Result<(), BitError> {
         if buf.len() << 3 < num_bits || self.num_bits_left() < num_bits {
             return Err(BitError::Overflow);
         }
@@ -130,9 +268,24 @@ impl<'a> BitReader<'a> {
         let mut bits_left = num_bits;
         let mut bytes_written = 0;
 
+        // when byte-aligned whole bytes can be copied as is.
+        if self.is_byte_aligned() {
+            let start = self.cur_bit >> 3;
+            let num_bytes = bits_left >> 3;
+            let src = self
+                .data
+                .get(start..start + num_bytes)
+                .ok_or(BitError::Overflow)?;
+            buf[..num_bytes].copy_from_slice(src);
+
+            self.cur_bit += num_bytes << 3;
+            bytes_written = num_bytes;
+            bits_left -= num_bytes << 3;
+        }
+
         while bits_left >= 64 {
             let value = self.read_ubit64(64)?;
-            let bytes = value.to_ne_bytes();
+            let bytes = value.to_le_bytes();
 
             let dest_range = bytes_written..bytes_written + 8;
             buf[dest_range].copy_from_slice(&bytes);
@@ -154,60 +307,35 @@ impl<'a> BitReader<'a> {
         Ok(())
     }
 
-    pub fn read_bytes(&mut self, buf: &mut [u8]) -> Result<(), BitError> {
-        self.read_bits(buf, buf.len() << 3)
-    }
-
-    /// this can save your ass when you're using `_unchecked` methods. once you're done reading
-    /// from buf call this to see if any bits were read from kyokai no kanata.
-    ///
-    /// returns [`Error::Overflow`] if overflowed (which means you are skrewed).
-    ///
-    /// i figured that returning result would be more convenient than a bool because it can be
-    /// questionmarked; plus, in some cases, this would eliminate a need of coming up with a custom
-    /// error.
-    pub fn is_overflowed(&self) -> Result<(), BitError> {
-        if self.cur_bit > self.num_bits {
-            Err(BitError::Overflow)
-        } else {
-            Ok(())
-        }
-    }
-
-    #[cfg(feature = "varint")]
-    pub fn read_uvarint<T>(&mut self) -> Result<T, BitError>
-    where
-        T: From<u8> + core::ops::BitOrAssign + core::ops::Shl<usize, Output = T>,
-    {
-        let byte = self.read_byte()?;
-        if (byte & CONTINUE_BIT) == 0 {
-            return Ok(T::from(byte));
-        }
-
-        let mut value = T::from(byte & 0x7f);
-        for count in 1..=max_varint_size::<T>() {
-            let byte = self.read_byte()?;
-            value |= (T::from(byte & PAYLOAD_BITS)) << (count * 7);
-            if (byte & CONTINUE_BIT) == 0 {
-                return Ok(value);
-            }
+    fn read_ubit64_into(&mut self, buf: &mut [u64], num_bits: usize) -> Result<(), BitError> {
+        let total_bits = buf.len().checked_mul(num_bits).ok_or(BitError::Overflow)?;
+        if num_bits > 64 || self.num_bits_left() < total_bits {
+            return Err(BitError::Overflow);
         }
 
-        Err(BitError::MalformedVarint)
+        self.read_ubit64_blocks(buf.len(), num_bits, |i, value| buf[i] = value)
     }
 
-    #[cfg(feature = "varint")]
-    pub fn read_varint64(&mut self) -> Result<i64, BitError> {
-        self.read_uvarint().map(zigzag_decode64)
-    }
+    #[allow(clippy::cast_possible_truncation)]
+    fn read_u32s_into(&mut self, buf: &mut [u32], num_bits: usize) -> Result<(), BitError> {
+        let total_bits = buf.len().checked_mul(num_bits).ok_or(BitError::Overflow)?;
+        if num_bits > 32 || self.num_bits_left() < total_bits {
+            return Err(BitError::Overflow);
+        }
 
-    #[cfg(feature = "varint")]
-    pub fn read_uvarint32(&mut self) -> Result<u32, BitError> {
-        self.read_uvarint()
+        // values are masked to at most 32 bits, truncation is lossless.
+        self.read_ubit64_blocks(buf.len(), num_bits, |i, value| buf[i] = value as u32)
     }
+}
 
-    #[cfg(feature = "varint")]
-    pub fn read_varint32(&mut self) -> Result<i32, BitError> {
-        self.read_uvarint32().map(zigzag_decode32)
+/// reads whole bytes starting at the current bit, which does not need to be byte-aligned. trailing
+/// bits that do not make up a full byte are not returned.
+#[cfg(feature = "std")]
+impl std::io::Read for BitReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = buf.len().min(self.num_bytes_left());
+        self.read_bytes(&mut buf[..n])
+            .map_err(std::io::Error::other)?;
+        Ok(n)
     }
 }
@@ -0,0 +1,421 @@
+// public/bitvec.h
+// partial port of CBitVec / CVarBitVec.
+
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+use core::ops::{BitAndAssign, BitOrAssign, BitXorAssign, Not};
+
+use crate::{BitError, BitRead, BitWrite, EXTRA_MASKS, get_bit_for_bit_num};
+
+/// returns the number of 64-bit words needed to hold `num_bits` bits. meant for sizing
+/// [`BitVec`]: `BitVec<MAX_EDICTS, { words_for_bits(MAX_EDICTS) }>`.
+#[must_use]
+pub const fn words_for_bits(num_bits: usize) -> usize {
+    num_bits.div_ceil(64)
+}
+
+fn test(words: &[u64], bit: usize) -> bool {
+    words[bit >> 6] & get_bit_for_bit_num(bit) != 0
+}
+
+fn set(words: &mut [u64], bit: usize) {
+    words[bit >> 6] |= get_bit_for_bit_num(bit);
+}
+
+fn clear(words: &mut [u64], bit: usize) {
+    words[bit >> 6] &= !get_bit_for_bit_num(bit);
+}
+
+fn count_ones(words: &[u64]) -> usize {
+    words.iter().map(|word| word.count_ones() as usize).sum()
+}
+
+fn find_next_set_bit(words: &[u64], start_bit: usize) -> Option<usize> {
+    let mut word_idx = start_bit >> 6;
+    // drop bits below start_bit in the first word.
+    let mut word = *words.get(word_idx)? & !EXTRA_MASKS[start_bit & 63];
+    loop {
+        if word != 0 {
+            return Some((word_idx << 6) + word.trailing_zeros() as usize);
+        }
+        word_idx += 1;
+        word = *words.get(word_idx)?;
+    }
+}
+
+fn iter_ones(words: &[u64]) -> impl Iterator<Item = usize> + '_ {
+    let mut next = find_next_set_bit(words, 0);
+    core::iter::from_fn(move || {
+        let bit = next?;
+        next = find_next_set_bit(words, bit + 1);
+        Some(bit)
+    })
+}
+
+/// reads `num_bits` bits into `words`. bits at `num_bits` and higher are cleared.
+fn read_from(words: &mut [u64], br: &mut impl BitRead, num_bits: usize) -> Result<(), BitError> {
+    if num_bits > words.len() << 6 || br.num_bits_left() < num_bits {
+        return Err(BitError::Overflow);
+    }
+
+    let full_words = num_bits >> 6;
+    br.read_ubit64_into(&mut words[..full_words], 64)?;
+    if let Some(word) = words.get_mut(full_words) {
+        *word = br.read_ubit64(num_bits & 63)?;
+    }
+    words
+        .iter_mut()
+        .skip(full_words + 1)
+        .for_each(|word| *word = 0);
+
+    Ok(())
+}
+
+/// writes the first `num_bits` bits of `words`.
+fn write_to(words: &[u64], bw: &mut impl BitWrite, num_bits: usize) -> Result<(), BitError> {
+    if num_bits > words.len() << 6 || bw.num_bits_left() < num_bits {
+        return Err(BitError::Overflow);
+    }
+
+    let full_words = num_bits >> 6;
+    bw.write_ubit64_from(&words[..full_words], 64)?;
+    if num_bits & 63 != 0 {
+        bw.write_ubit64(words[full_words], num_bits & 63)?;
+    }
+
+    Ok(())
+}
+
+/// a fixed-size bit vector of `N` bits, stored in `W` 64-bit words. `W` must be
+/// [`words_for_bits`]`(N)`; stable rust cannot compute it from `N` in the type, so it is spelled
+/// out: `BitVec<MAX_EDICTS, { words_for_bits(MAX_EDICTS) }>`.
+///
+/// methods that take a bit index panic if it is out of bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BitVec<const N: usize, const W: usize> {
+    words: [u64; W],
+}
+
+impl<const N: usize, const W: usize> Default for BitVec<N, W> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize, const W: usize> BitVec<N, W> {
+    pub const NUM_BITS: usize = N;
+
+    const WORDS_MATCH_BITS: () = assert!(
+        W == words_for_bits(N),
+        "the number of words must be words_for_bits(N)"
+    );
+
+    /// creates a bit vector with all bits cleared.
+    #[must_use]
+    pub const fn new() -> Self {
+        let () = Self::WORDS_MATCH_BITS;
+        Self { words: [0; W] }
+    }
+
+    /// bits past `N` in the last word are ignored.
+    #[must_use]
+    pub fn from_words(words: [u64; W]) -> Self {
+        let () = Self::WORDS_MATCH_BITS;
+        let mut bv = Self { words };
+        bv.clear_tail();
+        bv
+    }
+
+    #[must_use]
+    pub fn as_words(&self) -> &[u64; W] {
+        &self.words
+    }
+
+    #[must_use]
+    pub fn num_bits(&self) -> usize {
+        N
+    }
+
+    /// keeps bits past `N` in the last word cleared, so that they do not show up in counts,
+    /// searches or comparisons.
+    fn clear_tail(&mut self) {
+        if let Some(last) = self.words.last_mut()
+            && N & 63 != 0
+        {
+            *last &= EXTRA_MASKS[N & 63];
+        }
+    }
+
+    /// # Panics
+    ///
+    /// panics if `bit` is out of bounds.
+    #[must_use]
+    pub fn test(&self, bit: usize) -> bool {
+        assert!(bit < N, "bit index out of bounds");
+        test(&self.words, bit)
+    }
+
+    /// # Panics
+    ///
+    /// panics if `bit` is out of bounds.
+    pub fn set(&mut self, bit: usize) {
+        assert!(bit < N, "bit index out of bounds");
+        set(&mut self.words, bit);
+    }
+
+    /// # Panics
+    ///
+    /// panics if `bit` is out of bounds.
+    pub fn clear(&mut self, bit: usize) {
+        assert!(bit < N, "bit index out of bounds");
+        clear(&mut self.words, bit);
+    }
+
+    pub fn set_all(&mut self) {
+        self.words = [u64::MAX; W];
+        self.clear_tail();
+    }
+
+    pub fn clear_all(&mut self) {
+        self.words = [0; W];
+    }
+
+    #[must_use]
+    pub fn is_all_clear(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    /// population count.
+    #[must_use]
+    pub fn count_ones(&self) -> usize {
+        count_ones(&self.words)
+    }
+
+    /// returns the first set bit at or after `start_bit`.
+    #[must_use]
+    pub fn find_next_set_bit(&self, start_bit: usize) -> Option<usize> {
+        find_next_set_bit(&self.words, start_bit)
+    }
+
+    /// iterates over the indices of set bits, in ascending order.
+    pub fn iter_ones(&self) -> impl Iterator<Item = usize> + '_ {
+        iter_ones(&self.words)
+    }
+
+    /// replaces the contents with `num_bits` bits read from `br`; the rest is cleared.
+    pub fn read_from(&mut self, br: &mut impl BitRead, num_bits: usize) -> Result<(), BitError> {
+        if num_bits > N {
+            return Err(BitError::Overflow);
+        }
+        read_from(&mut self.words, br, num_bits)
+    }
+
+    /// writes the first `num_bits` bits to `bw`.
+    pub fn write_to(&self, bw: &mut impl BitWrite, num_bits: usize) -> Result<(), BitError> {
+        if num_bits > N {
+            return Err(BitError::Overflow);
+        }
+        write_to(&self.words, bw, num_bits)
+    }
+}
+
+impl<const N: usize, const W: usize> Not for BitVec<N, W> {
+    type Output = Self;
+
+    fn not(mut self) -> Self {
+        self.words.iter_mut().for_each(|word| *word = !*word);
+        self.clear_tail();
+        self
+    }
+}
+
+impl<const N: usize, const W: usize> BitAndAssign<&Self> for BitVec<N, W> {
+    fn bitand_assign(&mut self, rhs: &Self) {
+        self.words
+            .iter_mut()
+            .zip(rhs.words)
+            .for_each(|(word, rhs)| *word &= rhs);
+    }
+}
+
+impl<const N: usize, const W: usize> BitOrAssign<&Self> for BitVec<N, W> {
+    fn bitor_assign(&mut self, rhs: &Self) {
+        self.words
+            .iter_mut()
+            .zip(rhs.words)
+            .for_each(|(word, rhs)| *word |= rhs);
+    }
+}
+
+impl<const N: usize, const W: usize> BitXorAssign<&Self> for BitVec<N, W> {
+    fn bitxor_assign(&mut self, rhs: &Self) {
+        self.words
+            .iter_mut()
+            .zip(rhs.words)
+            .for_each(|(word, rhs)| *word ^= rhs);
+    }
+}
+
+/// a bit vector whose size is chosen at runtime.
+///
+/// methods that take a bit index panic if it is out of bounds. binary operations panic if the
+/// sizes differ.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct DynBitVec {
+    words: Vec<u64>,
+    num_bits: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl DynBitVec {
+    /// creates a bit vector of `num_bits` bits, all cleared.
+    #[must_use]
+    pub fn new(num_bits: usize) -> Self {
+        Self {
+            words: vec![0; words_for_bits(num_bits)],
+            num_bits,
+        }
+    }
+
+    #[must_use]
+    pub fn as_words(&self) -> &[u64] {
+        &self.words
+    }
+
+    #[must_use]
+    pub fn num_bits(&self) -> usize {
+        self.num_bits
+    }
+
+    /// changes the size to `num_bits` bits. new bits are cleared.
+    pub fn resize(&mut self, num_bits: usize) {
+        self.words.resize(words_for_bits(num_bits), 0);
+        self.num_bits = num_bits;
+        self.clear_tail();
+    }
+
+    /// keeps bits past `num_bits` in the last word cleared, so that they do not show up in
+    /// counts, searches or comparisons.
+    fn clear_tail(&mut self) {
+        if let Some(last) = self.words.last_mut()
+            && self.num_bits & 63 != 0
+        {
+            *last &= EXTRA_MASKS[self.num_bits & 63];
+        }
+    }
+
+    /// # Panics
+    ///
+    /// panics if `bit` is out of bounds.
+    #[must_use]
+    pub fn test(&self, bit: usize) -> bool {
+        assert!(bit < self.num_bits, "bit index out of bounds");
+        test(&self.words, bit)
+    }
+
+    /// # Panics
+    ///
+    /// panics if `bit` is out of bounds.
+    pub fn set(&mut self, bit: usize) {
+        assert!(bit < self.num_bits, "bit index out of bounds");
+        set(&mut self.words, bit);
+    }
+
+    /// # Panics
+    ///
+    /// panics if `bit` is out of bounds.
+    pub fn clear(&mut self, bit: usize) {
+        assert!(bit < self.num_bits, "bit index out of bounds");
+        clear(&mut self.words, bit);
+    }
+
+    pub fn set_all(&mut self) {
+        self.words.fill(u64::MAX);
+        self.clear_tail();
+    }
+
+    pub fn clear_all(&mut self) {
+        self.words.fill(0);
+    }
+
+    #[must_use]
+    pub fn is_all_clear(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    /// population count.
+    #[must_use]
+    pub fn count_ones(&self) -> usize {
+        count_ones(&self.words)
+    }
+
+    /// returns the first set bit at or after `start_bit`.
+    #[must_use]
+    pub fn find_next_set_bit(&self, start_bit: usize) -> Option<usize> {
+        find_next_set_bit(&self.words, start_bit)
+    }
+
+    /// iterates over the indices of set bits, in ascending order.
+    pub fn iter_ones(&self) -> impl Iterator<Item = usize> + '_ {
+        iter_ones(&self.words)
+    }
+
+    /// resizes to `num_bits` and replaces the contents with `num_bits` bits read from `br`.
+    pub fn read_from(&mut self, br: &mut impl BitRead, num_bits: usize) -> Result<(), BitError> {
+        if br.num_bits_left() < num_bits {
+            return Err(BitError::Overflow);
+        }
+        self.resize(num_bits);
+        read_from(&mut self.words, br, num_bits)
+    }
+
+    /// writes all bits to `bw`.
+    pub fn write_to(&self, bw: &mut impl BitWrite) -> Result<(), BitError> {
+        write_to(&self.words, bw, self.num_bits)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Not for DynBitVec {
+    type Output = Self;
+
+    fn not(mut self) -> Self {
+        self.words.iter_mut().for_each(|word| *word = !*word);
+        self.clear_tail();
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl BitAndAssign<&Self> for DynBitVec {
+    fn bitand_assign(&mut self, rhs: &Self) {
+        assert_eq!(self.num_bits, rhs.num_bits, "bit vector sizes differ");
+        self.words
+            .iter_mut()
+            .zip(&rhs.words)
+            .for_each(|(word, rhs)| *word &= rhs);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl BitOrAssign<&Self> for DynBitVec {
+    fn bitor_assign(&mut self, rhs: &Self) {
+        assert_eq!(self.num_bits, rhs.num_bits, "bit vector sizes differ");
+        self.words
+            .iter_mut()
+            .zip(&rhs.words)
+            .for_each(|(word, rhs)| *word |= rhs);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl BitXorAssign<&Self> for DynBitVec {
+    fn bitxor_assign(&mut self, rhs: &Self) {
+        assert_eq!(self.num_bits, rhs.num_bits, "bit vector sizes differ");
+        self.words
+            .iter_mut()
+            .zip(&rhs.words)
+            .for_each(|(word, rhs)| *word ^= rhs);
+    }
+}
@@ -0,0 +1,314 @@
+#[cfg(feature = "varint")]
+use dungers_varint::{MAX_VARINT_LEN, Sleb128, Varint, wire::Tag};
+
+use crate::{BitError, EXTRA_MASKS};
+
+/// a range of bits reserved by [`BitWrite::reserve`], to be filled in later with
+/// [`BitWrite::fill`].
+#[must_use = "reserved bits are zero until filled"]
+#[derive(Debug)]
+pub struct Reservation {
+    bit: usize,
+    num_bits: usize,
+}
+
+/// a sink of bits.
+///
+/// implementors only need to provide the required core (position tracking, [`Self::seek`] and
+/// [`Self::write_ubit64`]); everything else is built on top of it and can be overridden where a
+/// faster implementation is possible.
+pub trait BitWrite {
+    #[must_use]
+    fn num_bits_left(&self) -> usize;
+
+    #[must_use]
+    fn num_bits_written(&self) -> usize;
+
+    /// seek to a specific bit.
+    fn seek(&mut self, bit: usize) -> Result<(), BitError>;
+
+    /// writes the lowest `num_bits` bits of `data`; higher bits are ignored. the function can
+    /// write up to a maximum of 64 bits at a time.
+    fn write_ubit64(&mut self, data: u64, num_bits: usize) -> Result<(), BitError>;
+
+    #[must_use]
+    fn num_bytes_left(&self) -> usize {
+        self.num_bits_left() >> 3
+    }
+
+    #[must_use]
+    fn num_bytes_written(&self) -> usize {
+        (self.num_bits_written() + 7) >> 3
+    }
+
+    /// seek to an offset from the current position.
+    fn seek_relative(&mut self, bit_delta: isize) -> Result<usize, BitError> {
+        let bit = isize::try_from(self.num_bits_written())? + bit_delta;
+        self.seek(bit.try_into()?)?;
+        Ok(self.num_bits_written())
+    }
+
+    /// reserves `num_bits` bits at the current position (zeroing them) and moves past them. the
+    /// value can be written later with [`Self::fill`], e.g. once the length of what follows is
+    /// known.
+    fn reserve(&mut self, num_bits: usize) -> Result<Reservation, BitError> {
+        if num_bits > 64 {
+            return Err(BitError::Overflow);
+        }
+        let bit = self.num_bits_written();
+        self.write_ubit64(0, num_bits)?;
+        Ok(Reservation { bit, num_bits })
+    }
+
+    /// writes `value` into previously reserved bits without moving the current position.
+    ///
+    /// returns [`BitError::Overflow`] if `value` does not fit into the reservation.
+    fn fill(&mut self, reservation: Reservation, value: u64) -> Result<(), BitError> {
+        if value & !EXTRA_MASKS[reservation.num_bits] != 0 {
+            return Err(BitError::Overflow);
+        }
+        let cur_bit = self.num_bits_written();
+        self.seek(reservation.bit)?;
+        self.write_ubit64(value, reservation.num_bits)?;
+        self.seek(cur_bit)
+    }
+
+    /// writes a `num_bits` wide prefix holding the length in bits of whatever `f` writes after it.
+    /// returns the length.
+    fn write_bit_length_prefixed<F>(&mut self, num_bits: usize, f: F) -> Result<usize, BitError>
+    where
+        F: FnOnce(&mut Self) -> Result<(), BitError>,
+    {
+        let reservation = self.reserve(num_bits)?;
+        let start = self.num_bits_written();
+        f(self)?;
+        let len = self.num_bits_written() - start;
+        self.fill(reservation, len.try_into()?)?;
+        Ok(len)
+    }
+
+    /// writes a `num_bits` wide prefix holding the length in bytes of whatever `f` writes after
+    /// it. if `f` does not write a whole number of bytes, the body is padded with zero bits.
+    /// returns the length.
+    fn write_byte_length_prefixed<F>(&mut self, num_bits: usize, f: F) -> Result<usize, BitError>
+    where
+        F: FnOnce(&mut Self) -> Result<(), BitError>,
+    {
+        let reservation = self.reserve(num_bits)?;
+        let start = self.num_bits_written();
+        f(self)?;
+        let len = (self.num_bits_written() - start).div_ceil(8);
+        let padding = (len << 3) - (self.num_bits_written() - start);
+        if padding > 0 {
+            self.write_ubit64(0, padding)?;
+        }
+        self.fill(reservation, len.try_into()?)?;
+        Ok(len)
+    }
+
+    fn write_bool(&mut self, data: bool) -> Result<(), BitError> {
+        self.write_ubit64(u64::from(data), 1)
+    }
+
+    fn write_byte(&mut self, data: u8) -> Result<(), BitError> {
+        self.write_ubit64(u64::from(data), 8)
+    }
+
+    fn write_bits(&mut self, buf: &[u8], num_bits: usize) -> Result<(), BitError> {
+        if buf.len() << 3 < num_bits || self.num_bits_left() < num_bits {
+            return Err(BitError::Overflow);
+        }
+
+        let mut bits_left = num_bits;
+        let mut bytes_read = 0;
+
+        while bits_left >= 64 {
+            let mut bytes = [0u8; 8];
+            bytes.copy_from_slice(&buf[bytes_read..bytes_read + 8]);
+            self.write_ubit64(u64::from_le_bytes(bytes), 64)?;
+
+            bytes_read += 8;
+            bits_left -= 64;
+        }
+
+        while bits_left >= 8 {
+            self.write_byte(buf[bytes_read])?;
+            bytes_read += 1;
+            bits_left -= 8;
+        }
+
+        if bits_left > 0 {
+            self.write_ubit64(u64::from(buf[bytes_read]), bits_left)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_bytes(&mut self, buf: &[u8]) -> Result<(), BitError> {
+        self.write_bits(buf, buf.len() << 3)
+    }
+
+    /// writes the lowest `num_bits` bits of each value in `buf`. bounds are checked once, up
+    /// front; if there is not enough room nothing is written.
+    fn write_ubit64_from(&mut self, buf: &[u64], num_bits: usize) -> Result<(), BitError> {
+        let total_bits = buf.len().checked_mul(num_bits).ok_or(BitError::Overflow)?;
+        if num_bits > 64 || self.num_bits_left() < total_bits {
+            return Err(BitError::Overflow);
+        }
+
+        for &data in buf {
+            self.write_ubit64(data, num_bits)?;
+        }
+
+        Ok(())
+    }
+
+    /// same as [`Self::write_ubit64_from`], but for values of up to 32 bits.
+    fn write_u32s_from(&mut self, buf: &[u32], num_bits: usize) -> Result<(), BitError> {
+        let total_bits = buf.len().checked_mul(num_bits).ok_or(BitError::Overflow)?;
+        if num_bits > 32 || self.num_bits_left() < total_bits {
+            return Err(BitError::Overflow);
+        }
+
+        for &data in buf {
+            self.write_ubit64(u64::from(data), num_bits)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_u16(&mut self, data: u16) -> Result<(), BitError> {
+        self.write_ubit64(u64::from(data), 16)
+    }
+
+    fn write_u32(&mut self, data: u32) -> Result<(), BitError> {
+        self.write_ubit64(u64::from(data), 32)
+    }
+
+    fn write_u64(&mut self, data: u64) -> Result<(), BitError> {
+        self.write_ubit64(data, 64)
+    }
+
+    fn write_i8(&mut self, data: i8) -> Result<(), BitError> {
+        self.write_byte(data.cast_unsigned())
+    }
+
+    fn write_i16(&mut self, data: i16) -> Result<(), BitError> {
+        self.write_u16(data.cast_unsigned())
+    }
+
+    fn write_i32(&mut self, data: i32) -> Result<(), BitError> {
+        self.write_u32(data.cast_unsigned())
+    }
+
+    fn write_i64(&mut self, data: i64) -> Result<(), BitError> {
+        self.write_u64(data.cast_unsigned())
+    }
+
+    fn write_f32(&mut self, data: f32) -> Result<(), BitError> {
+        self.write_u32(data.to_bits())
+    }
+
+    fn write_f64(&mut self, data: f64) -> Result<(), BitError> {
+        self.write_u64(data.to_bits())
+    }
+
+    /// writes `data` followed by a null terminator. `data` must not contain nulls itself.
+    fn write_string(&mut self, data: &[u8]) -> Result<(), BitError> {
+        debug_assert!(!data.contains(&0));
+        self.write_bytes(data)?;
+        self.write_byte(0)
+    }
+
+    // NOTE: ref impl for varints:
+    // https://github.com/rust-lang/rust/blob/e5b3e68abf170556b9d56c6f9028318e53c9f06b/compiler/rustc_serialize/src/leb128.rs
+
+    /// writes `value` varint encoded; signed types are zigzag encoded. see [`Varint`].
+    #[cfg(feature = "varint")]
+    fn write_varint<T: Varint>(&mut self, value: T) -> Result<(), BitError> {
+        let mut buf = [0u8; MAX_VARINT_LEN];
+        let n = value.encode(&mut buf)?;
+        self.write_bytes(&buf[..n])
+    }
+
+    #[cfg(feature = "varint")]
+    fn write_uvarint64(&mut self, value: u64) -> Result<(), BitError> {
+        self.write_varint(value)
+    }
+
+    #[cfg(feature = "varint")]
+    fn write_varint64(&mut self, data: i64) -> Result<(), BitError> {
+        self.write_varint(data)
+    }
+
+    #[cfg(feature = "varint")]
+    fn write_uvarint32(&mut self, value: u32) -> Result<(), BitError> {
+        self.write_varint(value)
+    }
+
+    #[cfg(feature = "varint")]
+    fn write_varint32(&mut self, value: i32) -> Result<(), BitError> {
+        self.write_varint(value)
+    }
+
+    /// writes `value` as signed LEB128; see [`Sleb128`].
+    #[cfg(feature = "varint")]
+    fn write_sleb128<T: Sleb128>(&mut self, value: T) -> Result<(), BitError> {
+        let mut buf = [0u8; MAX_VARINT_LEN];
+        let n = value.encode_sleb128(&mut buf)?;
+        self.write_bytes(&buf[..n])
+    }
+
+    /// writes a protobuf field tag; see [`dungers_varint::wire`].
+    #[cfg(feature = "varint")]
+    fn write_tag(&mut self, tag: Tag) -> Result<(), BitError> {
+        self.write_varint(tag.to_u32()?)
+    }
+
+    /// writes `data` prefixed with its length as a varint, i.e. a protobuf length-delimited value.
+    #[cfg(feature = "varint")]
+    fn write_length_delimited(&mut self, data: &[u8]) -> Result<(), BitError> {
+        self.write_varint(u32::try_from(data.len())?)?;
+        self.write_bytes(data)
+    }
+
+    /// writes `values` as a protobuf packed repeated field, including the length prefix.
+    #[cfg(feature = "varint")]
+    fn write_packed_varints<T: Varint>(&mut self, values: &[T]) -> Result<(), BitError> {
+        let len: usize = values.iter().map(|value| value.encoded_len()).sum();
+        self.write_varint(u32::try_from(len)?)?;
+        values
+            .iter()
+            .try_for_each(|&value| self.write_varint(value))
+    }
+}
+
+impl<W: BitWrite> BitWrite for &mut W {
+    fn num_bits_left(&self) -> usize {
+        (**self).num_bits_left()
+    }
+
+    fn num_bits_written(&self) -> usize {
+        (**self).num_bits_written()
+    }
+
+    fn seek(&mut self, bit: usize) -> Result<(), BitError> {
+        (**self).seek(bit)
+    }
+
+    fn write_ubit64(&mut self, data: u64, num_bits: usize) -> Result<(), BitError> {
+        (**self).write_ubit64(data, num_bits)
+    }
+
+    fn write_bits(&mut self, buf: &[u8], num_bits: usize) -> Result<(), BitError> {
+        (**self).write_bits(buf, num_bits)
+    }
+
+    fn write_ubit64_from(&mut self, buf: &[u64], num_bits: usize) -> Result<(), BitError> {
+        (**self).write_ubit64_from(buf, num_bits)
+    }
+
+    fn write_u32s_from(&mut self, buf: &[u32], num_bits: usize) -> Result<(), BitError> {
+        (**self).write_u32s_from(buf, num_bits)
+    }
+}
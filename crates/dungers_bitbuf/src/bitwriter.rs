@@ -1,11 +1,8 @@
-#[cfg(feature = "varint")]
-use dungers_varint::{CONTINUE_BIT, PAYLOAD_BITS, zigzag_encode64};
-
-use crate::{BIT_WRITE_MASKS, BitError, EXTRA_MASKS};
+use crate::{BIT_WRITE_MASKS, BitError, BitWrite, EXTRA_MASKS, load_block, store_block};
 
 pub struct BitWriter<'a> {
     data_bits: usize,
-    data: &'a mut [u64],
+    data: &'a mut [u8],
     cur_bit: usize,
 }
 
@@ -13,33 +10,72 @@ impl<'a> BitWriter<'a> {
     pub fn new(buf: &'a mut [u8]) -> Self {
         Self {
             data_bits: buf.len() << 3,
-            data: bytemuck::cast_slice_mut(buf),
+            data: buf,
             cur_bit: 0,
         }
     }
 
-    #[must_use]
-    pub fn num_bits_left(&self) -> usize {
-        self.data_bits - self.cur_bit
-    }
+    /// encodes `len` consecutive `num_bits` wide values obtained from `get`. the caller is
+    /// responsible for checking bounds.
+    ///
+    /// instead of going through [`BitWrite::write_ubit64`] for every value, this accumulates
+    /// whole blocks and stores each of them once.
+    #[allow(clippy::cast_possible_truncation)]
+    fn write_ubit64_blocks(
+        &mut self,
+        len: usize,
+        num_bits: usize,
+        get: impl Fn(usize) -> u64,
+    ) -> Result<(), BitError> {
+        debug_assert!(num_bits <= 64 && len * num_bits <= self.num_bits_left());
+
+        if len == 0 || num_bits == 0 {
+            return Ok(());
+        }
+
+        let mask = EXTRA_MASKS[num_bits];
+
+        let mut block_idx = self.cur_bit >> 6;
+        // number of bits in `block` that are already set.
+        let mut filled = self.cur_bit & 63;
+        // keep bits that precede the current bit.
+        let mut block =
+            load_block(self.data, block_idx).ok_or(BitError::Overflow)? & EXTRA_MASKS[filled];
+
+        for i in 0..len {
+            let data = get(i) & mask;
+            block |= data.unbounded_shl(filled as u32);
+            filled += num_bits;
+            if filled >= 64 {
+                store_block(self.data, block_idx, block).ok_or(BitError::Overflow)?;
+                block_idx += 1;
+                filled -= 64;
+                block = data.unbounded_shr((num_bits - filled) as u32);
+            }
+        }
 
-    #[must_use]
-    pub fn num_bytes_left(&self) -> usize {
-        self.num_bits_left() >> 3
+        // keep bits that follow the last written bit.
+        if filled > 0 {
+            let rest = load_block(self.data, block_idx).ok_or(BitError::Overflow)?;
+            block |= rest & !EXTRA_MASKS[filled];
+            store_block(self.data, block_idx, block).ok_or(BitError::Overflow)?;
+        }
+
+        self.cur_bit += len * num_bits;
+        Ok(())
     }
+}
 
-    #[must_use]
-    pub fn num_bits_written(&self) -> usize {
-        self.cur_bit
+impl BitWrite for BitWriter<'_> {
+    fn num_bits_left(&self) -> usize {
+        self.data_bits - self.cur_bit
     }
 
-    #[must_use]
-    pub fn num_bytes_written(&self) -> usize {
-        (self.cur_bit + 7) >> 3
+    fn num_bits_written(&self) -> usize {
+        self.cur_bit
     }
 
-    /// seek to a specific bit.
-    pub fn seek(&mut self, bit: usize) -> Result<(), BitError> {
+    fn seek(&mut self, bit: usize) -> Result<(), BitError> {
         if bit > self.data_bits {
             return Err(BitError::Overflow);
         }
@@ -47,20 +83,16 @@ impl<'a> BitWriter<'a> {
         Ok(())
     }
 
-    /// seek to an offset from the current position.
-    pub fn seek_relative(&mut self, bit_delta: isize) -> Result<usize, BitError> {
-        let bit = isize::try_from(self.cur_bit)? + bit_delta;
-        self.seek(bit.try_into()?)?;
-        Ok(self.cur_bit)
-    }
-
-    pub fn write_ubit64(&mut self, data: u64, n: usize) -> Result<(), BitError> {
+    fn write_ubit64(&mut self, data: u64, n: usize) -> Result<(), BitError> {
         // make sure that the requested number of bits to write is in bounds of u64.
         debug_assert!(n <= 64);
 
         if self.cur_bit + n > self.data_bits {
             return Err(BitError::Overflow);
         }
+        if n == 0 {
+            return Ok(());
+        }
 
         // erase bits at n and higher positions
         let data = data & EXTRA_MASKS[n];
@@ -70,10 +102,10 @@ impl<'a> BitWriter<'a> {
 
         // SAFETY: assert and check above ensure that we'll not go out of bounds.
 
-        let mut block1 = *self.data.get(block1_idx).ok_or(BitError::Overflow)?;
+        let mut block1 = load_block(self.data, block1_idx).ok_or(BitError::Overflow)?;
         block1 &= BIT_WRITE_MASKS[bit_offset][n];
         block1 |= data << bit_offset;
-        *self.data.get_mut(block1_idx).ok_or(BitError::Overflow)? = block1;
+        store_block(self.data, block1_idx, block1).ok_or(BitError::Overflow)?;
 
         // did it span a block?
         let bits_written = 64 - bit_offset;
@@ -83,10 +115,10 @@ impl<'a> BitWriter<'a> {
 
             let block2_idx = block1_idx + 1;
 
-            let mut block2 = *self.data.get(block2_idx).ok_or(BitError::Overflow)?;
+            let mut block2 = load_block(self.data, block2_idx).ok_or(BitError::Overflow)?;
             block2 &= BIT_WRITE_MASKS[0][n];
             block2 |= data;
-            *self.data.get_mut(block2_idx).ok_or(BitError::Overflow)? = block2;
+            store_block(self.data, block2_idx, block2).ok_or(BitError::Overflow)?;
         }
 
         self.cur_bit += n;
@@ -94,34 +126,80 @@ impl<'a> BitWriter<'a> {
         Ok(())
     }
 
-    pub fn write_byte(&mut self, data: u8) -> Result<(), BitError> {
-        self.write_ubit64(u64::from(data), 8)
-    }
+    fn write_bits(&mut self, buf: &[u8], num_bits: usize) -> Result<(), BitError> {
+        if buf.len() << 3 < num_bits || self.num_bits_left() < num_bits {
+            return Err(BitError::Overflow);
+        }
 
-    // NOTE: ref impl for varints:
-    // https://github.com/rust-lang/rust/blob/e5b3e68abf170556b9d56c6f9028318e53c9f06b/compiler/rustc_serialize/src/leb128.rs
+        let mut bits_left = num_bits;
+        let mut bytes_read = 0;
+
+        // when byte-aligned whole bytes can be copied as is.
+        if self.cur_bit.is_multiple_of(8) {
+            let start = self.cur_bit >> 3;
+            let num_bytes = bits_left >> 3;
+            let dst = self
+                .data
+                .get_mut(start..start + num_bytes)
+                .ok_or(BitError::Overflow)?;
+            dst.copy_from_slice(&buf[..num_bytes]);
+
+            self.cur_bit += num_bytes << 3;
+            bytes_read = num_bytes;
+            bits_left -= num_bytes << 3;
+        }
 
-    // TODO: varint funcs can be faster
+        while bits_left >= 64 {
+            let mut bytes = [0u8; 8];
+            bytes.copy_from_slice(&buf[bytes_read..bytes_read + 8]);
+            self.write_ubit64(u64::from_le_bytes(bytes), 64)?;
 
-    #[cfg(feature = "varint")]
-    pub fn write_uvarint64(&mut self, mut value: u64) -> Result<(), BitError> {
-        loop {
-            if value < u64::from(CONTINUE_BIT) {
-                self.write_byte(value.try_into()?)?;
-                break;
-            }
+            bytes_read += 8;
+            bits_left -= 64;
+        }
+
+        while bits_left >= 8 {
+            self.write_byte(buf[bytes_read])?;
+            bytes_read += 1;
+            bits_left -= 8;
+        }
 
-            self.write_byte(u8::try_from(
-                (value & u64::from(PAYLOAD_BITS)) | u64::from(CONTINUE_BIT),
-            )?)?;
-            value >>= 7;
+        if bits_left > 0 {
+            self.write_ubit64(u64::from(buf[bytes_read]), bits_left)?;
         }
 
         Ok(())
     }
 
-    #[cfg(feature = "varint")]
-    pub fn write_varint64(&mut self, data: i64) -> Result<(), BitError> {
-        self.write_uvarint64(zigzag_encode64(data))
+    fn write_ubit64_from(&mut self, buf: &[u64], num_bits: usize) -> Result<(), BitError> {
+        let total_bits = buf.len().checked_mul(num_bits).ok_or(BitError::Overflow)?;
+        if num_bits > 64 || self.num_bits_left() < total_bits {
+            return Err(BitError::Overflow);
+        }
+
+        self.write_ubit64_blocks(buf.len(), num_bits, |i| buf[i])
+    }
+
+    fn write_u32s_from(&mut self, buf: &[u32], num_bits: usize) -> Result<(), BitError> {
+        let total_bits = buf.len().checked_mul(num_bits).ok_or(BitError::Overflow)?;
+        if num_bits > 32 || self.num_bits_left() < total_bits {
+            return Err(BitError::Overflow);
+        }
+
+        self.write_ubit64_blocks(buf.len(), num_bits, |i| u64::from(buf[i]))
+    }
+}
+
+/// writes whole bytes starting at the current bit, which does not need to be byte-aligned.
+#[cfg(feature = "std")]
+impl std::io::Write for BitWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = buf.len().min(self.num_bytes_left());
+        self.write_bytes(&buf[..n]).map_err(std::io::Error::other)?;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
     }
 }
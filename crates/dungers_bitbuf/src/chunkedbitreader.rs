@@ -0,0 +1,175 @@
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, vec};
+
+use crate::{BitError, BitRead, BitReader};
+
+/// a [`BitRead`] over a list of non-contiguous byte chunks, as if they were concatenated.
+///
+/// values are allowed to straddle chunk boundaries. reads that fit within a single chunk go
+/// through [`BitReader`] directly; only the ones that straddle take the slow path.
+pub struct ChunkedBitReader<'a> {
+    chunks: &'a [&'a [u8]],
+    num_bits: usize,
+    cur_bit: usize,
+    /// index of the chunk that contains `cur_bit`, or `chunks.len()` once everything was read.
+    chunk_idx: usize,
+    /// bit at which `chunk_idx` starts.
+    chunk_start_bit: usize,
+}
+
+impl<'a> ChunkedBitReader<'a> {
+    #[must_use]
+    pub fn new(chunks: &'a [&'a [u8]]) -> Self {
+        let mut ret = Self {
+            chunks,
+            num_bits: chunks.iter().map(|chunk| chunk.len() << 3).sum(),
+            cur_bit: 0,
+            chunk_idx: 0,
+            chunk_start_bit: 0,
+        };
+        ret.sync_chunk();
+        ret
+    }
+
+    /// moves `chunk_idx` forward until it points at the chunk that contains `cur_bit`, skipping
+    /// empty chunks.
+    fn sync_chunk(&mut self) {
+        while let Some(chunk) = self.chunks.get(self.chunk_idx) {
+            let chunk_end_bit = self.chunk_start_bit + (chunk.len() << 3);
+            if self.cur_bit < chunk_end_bit {
+                break;
+            }
+            self.chunk_idx += 1;
+            self.chunk_start_bit = chunk_end_bit;
+        }
+    }
+
+    /// returns the current chunk and the bit offset within it.
+    fn cur_chunk(&self) -> (&'a [u8], usize) {
+        let chunk = self.chunks.get(self.chunk_idx).copied().unwrap_or_default();
+        (chunk, self.cur_bit - self.chunk_start_bit)
+    }
+
+    fn advance(&mut self, num_bits: usize) {
+        self.cur_bit += num_bits;
+        self.sync_chunk();
+    }
+
+    /// reads `len` bytes. borrows from the input when the current bit is byte-aligned and the
+    /// bytes are within a single chunk, otherwise copies.
+    #[cfg(feature = "alloc")]
+    pub fn read_slice(&mut self, len: usize) -> Result<Cow<'a, [u8]>, BitError> {
+        // check before allocating; `len` may come straight off the wire.
+        if self.num_bytes_left() < len {
+            return Err(BitError::Overflow);
+        }
+
+        let (chunk, chunk_bit) = self.cur_chunk();
+        if self.is_byte_aligned() && len <= ((chunk.len() << 3) - chunk_bit) >> 3 {
+            let slice = BitReader::new_at(chunk, chunk_bit).read_aligned_slice(len)?;
+            self.advance(len << 3);
+            return Ok(Cow::Borrowed(slice));
+        }
+
+        let mut buf = vec![0u8; len];
+        self.read_bytes(&mut buf)?;
+        Ok(Cow::Owned(buf))
+    }
+}
+
+impl BitRead for ChunkedBitReader<'_> {
+    fn num_bits_left(&self) -> usize {
+        self.num_bits - self.cur_bit
+    }
+
+    fn num_bits_read(&self) -> usize {
+        self.cur_bit
+    }
+
+    fn seek(&mut self, bit: usize) -> Result<(), BitError> {
+        if bit > self.num_bits {
+            return Err(BitError::Overflow);
+        }
+        if bit < self.chunk_start_bit {
+            self.chunk_idx = 0;
+            self.chunk_start_bit = 0;
+        }
+        self.cur_bit = bit;
+        self.sync_chunk();
+        Ok(())
+    }
+
+    fn read_ubit64(&mut self, num_bits: usize) -> Result<u64, BitError> {
+        if num_bits > 64 || self.num_bits_left() < num_bits {
+            return Err(BitError::Overflow);
+        }
+
+        let (chunk, chunk_bit) = self.cur_chunk();
+        let chunk_bits_left = (chunk.len() << 3) - chunk_bit;
+
+        // fast path: fits within the current chunk.
+        if num_bits <= chunk_bits_left {
+            let ret = BitReader::new_at(chunk, chunk_bit).read_ubit64(num_bits)?;
+            self.advance(num_bits);
+            return Ok(ret);
+        }
+
+        // slow path: straddles one or more chunk boundaries.
+        let mut ret = 0;
+        let mut bits_read = 0;
+        while bits_read < num_bits {
+            let (chunk, chunk_bit) = self.cur_chunk();
+            let n = ((chunk.len() << 3) - chunk_bit).min(num_bits - bits_read);
+            ret |= BitReader::new_at(chunk, chunk_bit).read_ubit64(n)? << bits_read;
+            bits_read += n;
+            self.advance(n);
+        }
+
+        Ok(ret)
+    }
+
+    fn read_bits(&mut self, buf: &mut [u8], num_bits: usize) -> Result<(), BitError> {
+        if buf.len() << 3 < num_bits || self.num_bits_left() < num_bits {
+            return Err(BitError::Overflow);
+        }
+
+        let mut bits_left = num_bits;
+        let mut bytes_written = 0;
+
+        while bits_left >= 8 {
+            let (chunk, chunk_bit) = self.cur_chunk();
+            let num_bytes = (((chunk.len() << 3) - chunk_bit) >> 3).min(bits_left >> 3);
+
+            if num_bytes == 0 {
+                // less than a byte left in this chunk.
+                buf[bytes_written] = self.read_byte()?;
+                bytes_written += 1;
+                bits_left -= 8;
+                continue;
+            }
+
+            BitReader::new_at(chunk, chunk_bit)
+                .read_bytes(&mut buf[bytes_written..bytes_written + num_bytes])?;
+            self.advance(num_bytes << 3);
+            bytes_written += num_bytes;
+            bits_left -= num_bytes << 3;
+        }
+
+        if bits_left > 0 {
+            buf[bytes_written] = self.read_ubit64(bits_left)?.try_into()?;
+        }
+
+        Ok(())
+    }
+}
+
+/// see [`BitReader`]'s [`std::io::Read`] impl.
+#[cfg(feature = "std")]
+impl std::io::Read for ChunkedBitReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = buf.len().min(self.num_bytes_left());
+        self.read_bytes(&mut buf[..n])
+            .map_err(std::io::Error::other)?;
+        Ok(n)
+    }
+}
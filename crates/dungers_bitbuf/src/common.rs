@@ -50,3 +50,40 @@ pub(crate) const EXTRA_MASKS: [u64; 65] = {
     }
     extra_masks
 };
+
+/// loads the little-endian 64-bit block at `idx`. a trailing partial block is padded with zeros,
+/// thus the underlying buffer does not need to be aligned or sized to a multiple of 8 bytes.
+///
+/// returns [`None`] if the block starts past the end of `data`.
+#[inline]
+pub(crate) fn load_block(data: &[u8], idx: usize) -> Option<u64> {
+    let tail = data.get(idx << 3..)?;
+    if let Some(block) = tail.first_chunk::<8>() {
+        return Some(u64::from_le_bytes(*block));
+    }
+    if tail.is_empty() {
+        return None;
+    }
+    let mut block = [0u8; 8];
+    block[..tail.len()].copy_from_slice(tail);
+    Some(u64::from_le_bytes(block))
+}
+
+/// stores `block` as the little-endian 64-bit block at `idx`. bytes that would land past the end
+/// of `data` are dropped.
+///
+/// returns [`None`] if the block starts past the end of `data`.
+#[inline]
+pub(crate) fn store_block(data: &mut [u8], idx: usize, block: u64) -> Option<()> {
+    let tail = data.get_mut(idx << 3..)?;
+    if let Some(dst) = tail.first_chunk_mut::<8>() {
+        *dst = block.to_le_bytes();
+        return Some(());
+    }
+    if tail.is_empty() {
+        return None;
+    }
+    let len = tail.len();
+    tail.copy_from_slice(&block.to_le_bytes()[..len]);
+    Some(())
+}
@@ -1,11 +1,33 @@
 #[derive(thiserror::Error, Debug)]
+#[non_exhaustive]
 pub enum BitError {
     #[error("was about to overrun a buffer")]
     Overflow,
     #[error("malformed varint")]
     MalformedVarint,
+    #[error("non-canonical varint: encoding is longer than necessary")]
+    NonCanonicalVarint,
+    #[error("varint overflows the target type")]
+    VarintOverflow,
+    #[error("invalid protobuf tag")]
+    InvalidTag,
+    #[error("not byte-aligned")]
+    Unaligned,
     #[error("buffer too small")]
     BufferTooSmall,
     #[error(transparent)]
     TryFromIntError(#[from] core::num::TryFromIntError),
 }
+
+#[cfg(feature = "varint")]
+impl From<dungers_varint::VarintError> for BitError {
+    fn from(err: dungers_varint::VarintError) -> Self {
+        match err {
+            dungers_varint::VarintError::NonCanonical => Self::NonCanonicalVarint,
+            dungers_varint::VarintError::Overflow => Self::VarintOverflow,
+            dungers_varint::VarintError::BufferTooSmall => Self::BufferTooSmall,
+            dungers_varint::VarintError::InvalidTag => Self::InvalidTag,
+            _ => Self::MalformedVarint,
+        }
+    }
+}
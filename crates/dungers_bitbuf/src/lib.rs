@@ -1,3 +1,4 @@
+#![no_std]
 #![deny(unsafe_code)]
 #![deny(clippy::all)]
 #![deny(unreachable_pub)]
@@ -12,14 +13,43 @@
 
 //! this is a partial port of valve's bitbuf. original implementation can be found on github
 //! <https://github.com/ValveSoftware/source-sdk-2013>.
+//!
+//! the crate is `no_std`. the `alloc` feature enables apis that allocate (owned readers,
+//! `DynBitVec`, `BitReader::read_slice`) and the default `std` feature additionally enables
+//! `std::io` impls.
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+mod bitdiff;
+mod bitread;
 mod bitreader;
+mod bitvec;
+mod bitwrite;
 mod bitwriter;
+mod chunkedbitreader;
 mod common;
 mod error;
+#[cfg(feature = "alloc")]
+mod ownedbitreader;
 
+pub use bitdiff::{BitDiff, BitSlice, diff_bits};
+pub use bitread::BitRead;
+#[cfg(feature = "varint")]
+pub use bitread::PackedVarints;
 pub use bitreader::BitReader;
+#[cfg(feature = "alloc")]
+pub use bitvec::DynBitVec;
+pub use bitvec::{BitVec, words_for_bits};
+pub use bitwrite::{BitWrite, Reservation};
 pub use bitwriter::BitWriter;
+pub use chunkedbitreader::ChunkedBitReader;
 pub use common::get_bit_for_bit_num;
-pub(crate) use common::{BIT_WRITE_MASKS, EXTRA_MASKS};
+pub(crate) use common::{BIT_WRITE_MASKS, EXTRA_MASKS, load_block, store_block};
 pub use error::BitError;
+#[cfg(feature = "bytes")]
+pub use ownedbitreader::BytesBitReader;
+#[cfg(feature = "alloc")]
+pub use ownedbitreader::{OwnedBitReader, SharedBitReader};
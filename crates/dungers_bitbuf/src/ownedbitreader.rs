@@ -0,0 +1,207 @@
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+use crate::{BitError, BitRead, BitReader};
+
+/// a [`BitReader`] that owns its input.
+///
+/// `B` can be anything that derefs into bytes: `Vec<u8>`, `Box<[u8]>`, `Arc<[u8]>`,
+/// `bytes::Bytes`, a memory map, etc. the buffer does not need to be aligned or padded. word
+/// buffers such as `Box<[u64]>` are converted with [`Self::from_words`].
+///
+/// implements [`BitRead`] just like [`BitReader`]; [`Self::as_bit_reader`] hands out borrowing
+/// views for code that takes a [`BitReader`].
+#[derive(Debug, Clone)]
+pub struct OwnedBitReader<B = Vec<u8>> {
+    data: B,
+    cur_bit: usize,
+}
+
+/// an [`OwnedBitReader`] that is cheap to clone. each clone has its own cursor, the buffer is
+/// shared.
+pub type SharedBitReader = OwnedBitReader<Arc<[u8]>>;
+
+/// an [`OwnedBitReader`] over [`bytes::Bytes`], keeping the buffer alive for as long as the
+/// reader is. aligned reads can hand out [`bytes::Bytes`] that share the buffer; see
+/// [`Self::read_aligned_bytes`].
+#[cfg(feature = "bytes")]
+pub type BytesBitReader = OwnedBitReader<bytes::Bytes>;
+
+impl<B: AsRef<[u8]>> OwnedBitReader<B> {
+    #[must_use]
+    pub fn new(data: B) -> Self {
+        Self { data, cur_bit: 0 }
+    }
+
+    #[must_use]
+    pub fn get_ref(&self) -> &B {
+        &self.data
+    }
+
+    #[must_use]
+    pub fn into_inner(self) -> B {
+        self.data
+    }
+
+    /// returns a [`BitReader`] that borrows the buffer and starts at the current bit. reading from
+    /// the view does not advance this reader.
+    #[must_use]
+    pub fn as_bit_reader(&self) -> BitReader<'_> {
+        BitReader::new_at(self.data.as_ref(), self.cur_bit)
+    }
+
+    /// runs `f` with a [`BitReader`] that starts at the current bit, then advances this reader to
+    /// wherever `f` left it.
+    pub fn with_bit_reader<'s, T>(&'s mut self, f: impl FnOnce(&mut BitReader<'s>) -> T) -> T {
+        let mut br = BitReader::new_at(self.data.as_ref(), self.cur_bit);
+        let ret = f(&mut br);
+        self.cur_bit = br.num_bits_read();
+        ret
+    }
+
+    /// see [`BitReader::read_aligned_slice`].
+    pub fn read_aligned_slice(&mut self, len: usize) -> Result<&[u8], BitError> {
+        self.with_bit_reader(|br| br.read_aligned_slice(len))
+    }
+
+    /// see [`BitReader::read_slice`].
+    pub fn read_slice(&mut self, len: usize) -> Result<Cow<'_, [u8]>, BitError> {
+        self.with_bit_reader(|br| br.read_slice(len))
+    }
+
+    /// see [`BitReader::find_pattern`].
+    pub fn find_pattern(&self, pattern: u64, width: usize) -> Result<Option<usize>, BitError> {
+        self.as_bit_reader().find_pattern(pattern, width)
+    }
+
+    /// see [`BitReader::find_any_pattern`].
+    pub fn find_any_pattern(
+        &self,
+        patterns: &[(u64, usize)],
+    ) -> Result<Option<(usize, usize)>, BitError> {
+        self.as_bit_reader().find_any_pattern(patterns)
+    }
+
+    /// see [`BitReader::is_overflowed`].
+    pub fn is_overflowed(&self) -> Result<(), BitError> {
+        self.as_bit_reader().is_overflowed()
+    }
+}
+
+impl<B: AsRef<[u8]>> BitRead for OwnedBitReader<B> {
+    fn num_bits_left(&self) -> usize {
+        self.as_bit_reader().num_bits_left()
+    }
+
+    fn num_bits_read(&self) -> usize {
+        self.cur_bit
+    }
+
+    fn seek(&mut self, bit: usize) -> Result<(), BitError> {
+        self.with_bit_reader(|br| br.seek(bit))
+    }
+
+    fn read_ubit64(&mut self, num_bits: usize) -> Result<u64, BitError> {
+        self.with_bit_reader(|br| br.read_ubit64(num_bits))
+    }
+
+    fn read_bool(&mut self) -> Result<bool, BitError> {
+        self.with_bit_reader(BitRead::read_bool)
+    }
+
+    fn read_bits(&mut self, buf: &mut [u8], num_bits: usize) -> Result<(), BitError> {
+        self.with_bit_reader(|br| br.read_bits(buf, num_bits))
+    }
+
+    fn read_ubit64_into(&mut self, buf: &mut [u64], num_bits: usize) -> Result<(), BitError> {
+        self.with_bit_reader(|br| br.read_ubit64_into(buf, num_bits))
+    }
+
+    fn read_u32s_into(&mut self, buf: &mut [u32], num_bits: usize) -> Result<(), BitError> {
+        self.with_bit_reader(|br| br.read_u32s_into(buf, num_bits))
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl OwnedBitReader<memmap2::Mmap> {
+    /// maps `file` into memory and reads from the mapping. the file's length does not need to be
+    /// a multiple of 8; the trailing partial block is handled without padding the mapping.
+    ///
+    /// # Safety
+    ///
+    /// see [`memmap2::Mmap::map`]. the file must not be modified (or truncated) while the mapping
+    /// is alive, by this or any other process.
+    #[allow(unsafe_code)]
+    pub unsafe fn map(file: &std::fs::File) -> std::io::Result<Self> {
+        // SAFETY: upheld by the caller.
+        let mmap = unsafe { memmap2::Mmap::map(file)? };
+        Ok(Self::new(mmap))
+    }
+
+    /// opens the file at `path` and maps it; see [`Self::map`].
+    ///
+    /// # Safety
+    ///
+    /// see [`Self::map`].
+    #[allow(unsafe_code)]
+    pub unsafe fn open(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        // SAFETY: upheld by the caller.
+        unsafe { Self::map(&file) }
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl BytesBitReader {
+    /// like [`Self::read_aligned_slice`], but returns a [`bytes::Bytes`] that shares the buffer
+    /// instead of borrowing from the reader.
+    pub fn read_aligned_bytes(&mut self, len: usize) -> Result<bytes::Bytes, BitError> {
+        let start = self.cur_bit >> 3;
+        self.read_aligned_slice(len)?;
+        Ok(self.data.slice(start..start + len))
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl From<bytes::Bytes> for BytesBitReader {
+    fn from(data: bytes::Bytes) -> Self {
+        Self::new(data)
+    }
+}
+
+impl OwnedBitReader {
+    /// copies `words` into a byte buffer. bits are numbered from the lowest bit of the first
+    /// word, the same way [`BitReader`] loads its blocks.
+    #[must_use]
+    pub fn from_words(words: &[u64]) -> Self {
+        Self::new(words.iter().flat_map(|word| word.to_le_bytes()).collect())
+    }
+}
+
+impl From<Vec<u8>> for OwnedBitReader {
+    fn from(data: Vec<u8>) -> Self {
+        Self::new(data)
+    }
+}
+
+impl From<Box<[u64]>> for OwnedBitReader {
+    fn from(words: Box<[u64]>) -> Self {
+        Self::from_words(&words)
+    }
+}
+
+impl From<Vec<u8>> for SharedBitReader {
+    fn from(data: Vec<u8>) -> Self {
+        Self::new(data.into())
+    }
+}
+
+/// see [`BitReader`]'s [`std::io::Read`] impl.
+#[cfg(feature = "std")]
+impl<B: AsRef<[u8]>> std::io::Read for OwnedBitReader<B> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.with_bit_reader(|br| std::io::Read::read(br, buf))
+    }
+}
@@ -0,0 +1,74 @@
+use dungers_bitbuf::{BitSlice, assert_bits_eq, diff_bits};
+
+#[test]
+fn test_diff_bits_equal() {
+    let a = [0xaa, 0xbb, 0xcc];
+    assert!(diff_bits(&a, &a).is_none());
+    assert_bits_eq!(&a, &vec![0xaa, 0xbb, 0xcc]);
+}
+
+#[test]
+fn test_diff_bits_first_difference() {
+    let mut a = vec![0u8; 40];
+    let mut b = a.clone();
+    a[9] = 0b0000_0100;
+    b[9] = 0b0000_0100;
+    b[20] = 0b0001_0000;
+    a[30] = 1;
+    b[30] = 1;
+
+    assert_eq!(diff_bits(&a, &b).unwrap().bit(), 20 * 8 + 4);
+}
+
+#[test]
+fn test_diff_bits_length() {
+    let a = [0xaa, 0xbb];
+    let b = [0xaa, 0xbb, 0xcc];
+
+    // a is a prefix of b.
+    assert_eq!(diff_bits(&a, &b).unwrap().bit(), 16);
+}
+
+#[test]
+fn test_diff_bits_ranges() {
+    // the same bits, shifted by 3.
+    let a = [0b1010_1000, 0b0000_0111];
+    let b = [0b1111_0101, 0b0000_0000];
+
+    let left = BitSlice::with_range(&a, 3, 10).unwrap();
+    let right = BitSlice::with_range(&b, 0, 10).unwrap();
+    assert!(diff_bits(left, right).is_none());
+
+    let right = BitSlice::with_range(&b, 0, 11).unwrap();
+    assert_eq!(diff_bits(left, right).unwrap().bit(), 10);
+
+    assert!(BitSlice::with_range(&a, 3, 14).is_err());
+}
+
+#[test]
+fn test_diff_display() {
+    let mut a = vec![0u8; 12];
+    let mut b = a.clone();
+    a[5] = 0b0000_0001;
+    b[5] = 0b1000_0001;
+    b[6] = 0b0000_0010;
+
+    let diff = diff_bits(&a, &b).unwrap();
+    assert_eq!(diff.bit(), 47);
+    assert_eq!(
+        diff.to_string(),
+        "\
+bits differ at 47 (left has 96 bits, right has 96 bits)
+     bit  left                                 right
+       0  00000000 00000000 00000000 00000000  00000000 00000000 00000000 00000000
+      32  00000000 10000000 00000000 00000000  00000000 10000001 01000000 00000000
+                          ^  ^                                 ^  ^
+      64  00000000 00000000 00000000 00000000  00000000 00000000 00000000 00000000"
+    );
+}
+
+#[test]
+#[should_panic(expected = "bits differ at 8 (left has 8 bits, right has 16 bits)")]
+fn test_assert_bits_eq_panics() {
+    assert_bits_eq!(&[0xaa], &[0xaa, 0xbb], "encoding {} differs", 1);
+}
@@ -0,0 +1,129 @@
+#[cfg(feature = "alloc")]
+use dungers_bitbuf::OwnedBitReader;
+use dungers_bitbuf::{BitError, BitRead, BitReader, BitWrite, BitWriter, ChunkedBitReader};
+
+/// a reader that only implements the required core, to exercise provided methods.
+struct Bits {
+    bits: Vec<bool>,
+    cur_bit: usize,
+}
+
+impl BitRead for Bits {
+    fn num_bits_left(&self) -> usize {
+        self.bits.len() - self.cur_bit
+    }
+
+    fn num_bits_read(&self) -> usize {
+        self.cur_bit
+    }
+
+    fn seek(&mut self, bit: usize) -> Result<(), BitError> {
+        if bit > self.bits.len() {
+            return Err(BitError::Overflow);
+        }
+        self.cur_bit = bit;
+        Ok(())
+    }
+
+    fn read_ubit64(&mut self, num_bits: usize) -> Result<u64, BitError> {
+        if num_bits > 64 || self.num_bits_left() < num_bits {
+            return Err(BitError::Overflow);
+        }
+        let mut ret = 0;
+        for i in 0..num_bits {
+            ret |= u64::from(self.bits[self.cur_bit + i]) << i;
+        }
+        self.cur_bit += num_bits;
+        Ok(ret)
+    }
+}
+
+fn write_record(bw: &mut impl BitWrite) -> Result<(), BitError> {
+    bw.write_bool(true)?;
+    bw.write_ubit64(0b101, 3)?;
+    bw.write_i8(-5)?;
+    bw.write_u16(0xbeef)?;
+    bw.write_i32(-123_456)?;
+    bw.write_u64(u64::MAX - 1)?;
+    bw.write_f32(1.5)?;
+    bw.write_f64(-0.25)?;
+    bw.write_string(b"hello")?;
+    bw.write_bytes(&[0xaa, 0xbb, 0xcc])
+}
+
+fn check_record(br: &mut impl BitRead) {
+    assert!(br.read_bool().unwrap());
+    assert_eq!(br.read_ubit64(3).unwrap(), 0b101);
+    assert_eq!(br.read_i8().unwrap(), -5);
+    assert_eq!(br.read_u16().unwrap(), 0xbeef);
+    assert_eq!(br.read_i32().unwrap(), -123_456);
+    assert_eq!(br.read_u64().unwrap(), u64::MAX - 1);
+    assert_eq!(br.read_f32().unwrap(), 1.5);
+    assert_eq!(br.read_f64().unwrap(), -0.25);
+
+    let mut s = [0u8; 8];
+    let len = br.read_string(&mut s).unwrap();
+    assert_eq!(&s[..len], b"hello");
+
+    let mut bytes = [0u8; 3];
+    br.read_bytes(&mut bytes).unwrap();
+    assert_eq!(bytes, [0xaa, 0xbb, 0xcc]);
+}
+
+fn encode_record() -> Vec<u8> {
+    let mut buf = vec![0u8; 64];
+    let mut bw = BitWriter::new(&mut buf);
+    write_record(&mut bw).unwrap();
+    let len = bw.num_bytes_written();
+    buf.truncate(len);
+    buf
+}
+
+#[test]
+fn test_generic_over_readers() {
+    let buf = encode_record();
+
+    check_record(&mut BitReader::new(&buf));
+    #[cfg(feature = "alloc")]
+    check_record(&mut OwnedBitReader::new(buf.clone()));
+
+    let (a, b) = buf.split_at(5);
+    let chunks = [a, b];
+    check_record(&mut ChunkedBitReader::new(&chunks));
+}
+
+#[test]
+fn test_provided_methods() {
+    let buf = encode_record();
+    let mut bits = Bits {
+        bits: (0..buf.len() << 3)
+            .map(|i| buf[i >> 3] >> (i & 7) & 1 == 1)
+            .collect(),
+        cur_bit: 0,
+    };
+
+    check_record(&mut bits);
+    let end = bits.num_bits_read();
+    assert_eq!(bits.seek_relative(-24).unwrap(), end - 24);
+    assert_eq!(bits.read_byte().unwrap(), 0xaa);
+    assert_eq!(bits.skip_bits(16).unwrap(), end);
+    assert_eq!(bits.align_to_byte().unwrap(), buf.len() << 3);
+    assert!(bits.read_bool().is_err());
+}
+
+#[test]
+fn test_read_string_buffer_too_small() {
+    let mut buf = [0u8; 16];
+    let mut bw = BitWriter::new(&mut buf);
+    bw.write_string(b"hello").unwrap();
+    bw.write_byte(0xaa).unwrap();
+
+    let mut br = BitReader::new(&buf);
+    let mut s = [0u8; 2];
+    assert!(matches!(
+        br.read_string(&mut s),
+        Err(BitError::BufferTooSmall)
+    ));
+    // the whole string is consumed regardless.
+    assert_eq!(br.read_byte().unwrap(), 0xaa);
+}
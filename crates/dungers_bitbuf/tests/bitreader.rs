@@ -1,4 +1,4 @@
-use dungers_bitbuf::BitReader;
+use dungers_bitbuf::{BitRead, BitReader};
 
 #[test]
 fn test_read_ubit64_overflow() {
@@ -89,3 +89,269 @@ fn test_read_bytes() {
     // try to read when no more bytes are available
     assert!(br.read_bytes(&mut out[0..1]).is_err());
 }
+
+#[test]
+fn test_read_ubit64_partial_trailing_block() {
+    // 11 bytes; the second block is only partially backed by the buffer.
+    let buf = [0u8, 0, 0, 0, 0, 0, 0, 0, 0xaa, 0xbb, 0xcc];
+    let mut br = BitReader::new(&buf);
+
+    br.read_ubit64(60).unwrap();
+    assert_eq!(br.read_ubit64(28).unwrap(), 0xccbbaa0);
+    assert!(br.read_ubit64(1).is_err());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_io_read_unaligned() {
+    use std::io::Read;
+
+    let buf = [0b1010_1000, 0b0000_0111, 0b1111_0000, 0b0000_0001];
+    let mut br = BitReader::new(&buf);
+
+    br.read_ubit64(3).unwrap();
+
+    let mut out = [0u8; 8];
+    // only 3 whole bytes are left; the trailing 5 bits are not returned.
+    assert_eq!(br.read(&mut out).unwrap(), 3);
+    assert_eq!(out[0..3], [0xf5, 0x00, 0x3e]);
+    assert_eq!(br.read(&mut out).unwrap(), 0);
+    assert_eq!(br.num_bits_left(), 5);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_io_read_exact() {
+    use std::io::{ErrorKind, Read};
+
+    let buf = [0xaa, 0xbb, 0xcc];
+    let mut br = BitReader::new(&buf);
+
+    let mut out = [0u8; 2];
+    br.read_exact(&mut out).unwrap();
+    assert_eq!(out, [0xaa, 0xbb]);
+
+    let err = br.read_exact(&mut out).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn test_read_aligned_slice() {
+    let buf = [0xaa, 0xbb, 0xcc, 0xdd];
+    let mut br = BitReader::new(&buf);
+
+    assert_eq!(br.read_aligned_slice(2).unwrap(), [0xaa, 0xbb]);
+    assert!(std::ptr::eq(
+        br.read_aligned_slice(1).unwrap().as_ptr(),
+        &buf[2]
+    ));
+    assert!(br.read_aligned_slice(2).is_err());
+
+    br.read_ubit64(1).unwrap();
+    assert!(matches!(
+        br.read_aligned_slice(0),
+        Err(dungers_bitbuf::BitError::Unaligned)
+    ));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_read_slice() {
+    use std::borrow::Cow;
+
+    let buf = [0b1010_1000, 0b0000_0111, 0b1111_0000, 0b0000_0001];
+    let mut br = BitReader::new(&buf);
+
+    assert!(matches!(
+        br.read_slice(1).unwrap(),
+        Cow::Borrowed([0b1010_1000])
+    ));
+
+    br.read_ubit64(3).unwrap();
+    let slice = br.read_slice(2).unwrap();
+    assert!(matches!(slice, Cow::Owned(_)));
+    assert_eq!(*slice, [0x00, 0x3e]);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_read_slice_overflow() {
+    let buf = [0u8; 4];
+    let mut br = BitReader::new(&buf);
+    br.read_ubit64(3).unwrap();
+
+    // must fail before allocating.
+    assert!(matches!(
+        br.read_slice(usize::MAX),
+        Err(dungers_bitbuf::BitError::Overflow)
+    ));
+    assert!(matches!(
+        br.read_slice(4),
+        Err(dungers_bitbuf::BitError::Overflow)
+    ));
+    assert_eq!(br.num_bits_read(), 3);
+    assert_eq!(br.read_slice(3).unwrap().len(), 3);
+}
+
+#[test]
+fn test_align_to_byte() {
+    let buf = [0u8; 2];
+    let mut br = BitReader::new(&buf);
+
+    assert_eq!(br.align_to_byte().unwrap(), 0);
+    br.read_ubit64(3).unwrap();
+    assert_eq!(br.align_to_byte().unwrap(), 8);
+    assert!(br.is_byte_aligned());
+}
+
+#[test]
+fn test_skip_bits() {
+    let buf = [0u8; 2];
+    let mut br = BitReader::new(&buf);
+
+    assert_eq!(br.skip_bits(5).unwrap(), 5);
+    assert_eq!(br.skip_bits(11).unwrap(), 16);
+    assert!(br.skip_bits(1).is_err());
+    assert_eq!(br.num_bits_read(), 16);
+}
+
+fn xorshift(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+#[test]
+fn test_read_ubit64_into_matches_per_call() {
+    let mut state = 0x2545f4914f6cdd1d;
+    // odd length, so that the last block is partial.
+    let odd: Vec<u8> = (0..523).map(|_| xorshift(&mut state) as u8).collect();
+
+    // the second buffer is a whole number of blocks, so a block loaded at its end would be out
+    // of bounds.
+    for buf in [&odd[..], &odd[..520]] {
+        for num_bits in 0..=64 {
+            for offset in [0, 1, 7, 63, buf.len() * 8] {
+                let len = (buf.len() * 8 - offset) / num_bits.max(1);
+                let len = if num_bits == 0 { 17 } else { len };
+
+                let mut want = BitReader::new(buf);
+                want.seek(offset).unwrap();
+                let want_values: Vec<u64> = (0..len)
+                    .map(|_| want.read_ubit64(num_bits).unwrap())
+                    .collect();
+
+                let mut got = BitReader::new(buf);
+                got.seek(offset).unwrap();
+                let mut got_values = vec![0; len];
+                got.read_ubit64_into(&mut got_values, num_bits).unwrap();
+
+                assert_eq!(
+                    got_values, want_values,
+                    "num_bits={num_bits} offset={offset}"
+                );
+                assert_eq!(got.num_bits_read(), want.num_bits_read());
+            }
+        }
+    }
+}
+
+#[test]
+fn test_read_u32s_into() {
+    let buf = [0b1010_1000, 0b0000_0111, 0b1111_0000, 0b0000_0001];
+    let mut br = BitReader::new(&buf);
+
+    let mut values = [0u32; 3];
+    br.read_u32s_into(&mut values, 10).unwrap();
+    assert_eq!(values, [0b11_1010_1000, 0b00_0000_0001, 0b00_0001_1111]);
+    assert!(br.read_u32s_into(&mut values, 33).is_err());
+
+    let buf = [0u8; 8];
+    let mut br = BitReader::new(&buf);
+    br.seek(64).unwrap();
+    br.read_u32s_into(&mut values, 0).unwrap();
+    assert_eq!(values, [0; 3]);
+}
+
+#[test]
+fn test_read_ubit64_into_overflow() {
+    let buf = [0xffu8; 8];
+    let mut br = BitReader::new(&buf);
+
+    let mut values = [0u64; 5];
+    assert!(br.read_ubit64_into(&mut values, 13).is_err());
+    // nothing was consumed.
+    assert_eq!(br.num_bits_read(), 0);
+    assert!(br.read_ubit64_into(&mut values[..4], 16).is_ok());
+}
+
+#[test]
+fn test_read_ubit64_zero_bits() {
+    let buf = [0xffu8; 16];
+    let mut br = BitReader::new(&buf);
+
+    assert_eq!(br.read_ubit64(0).unwrap(), 0);
+    br.read_ubit64(64).unwrap();
+    assert_eq!(br.read_ubit64(0).unwrap(), 0);
+    assert_eq!(br.num_bits_read(), 64);
+}
+
+#[test]
+fn test_find_pattern() {
+    use dungers_bitbuf::{BitWrite, BitWriter};
+
+    const MARKER: u64 = 0x1d_cafe;
+
+    let mut buf = [0u8; 37];
+    let mut bw = BitWriter::new(&mut buf);
+    bw.seek(77).unwrap();
+    bw.write_ubit64(MARKER, 21).unwrap();
+    bw.seek(250).unwrap();
+    bw.write_ubit64(MARKER, 21).unwrap();
+
+    let mut br = BitReader::new(&buf);
+    assert_eq!(br.find_pattern(MARKER, 21).unwrap(), Some(77));
+    // the cursor does not move.
+    assert_eq!(br.num_bits_read(), 0);
+
+    br.seek(78).unwrap();
+    let bit = br.find_pattern(MARKER, 21).unwrap().unwrap();
+    assert_eq!(bit, 250);
+    br.seek(bit).unwrap();
+    assert_eq!(br.read_ubit64(21).unwrap(), MARKER);
+
+    assert_eq!(br.find_pattern(MARKER, 21).unwrap(), None);
+}
+
+#[test]
+fn test_find_pattern_at_end() {
+    let buf = [0u8, 0, 0, 0, 0, 0, 0, 0, 0, 0xb0];
+    let mut br = BitReader::new(&buf);
+
+    // 0b1011 sits in the last 4 bits; a wider pattern would run past the end.
+    assert_eq!(br.find_pattern(0b1011, 4).unwrap(), Some(76));
+    assert_eq!(br.find_pattern(0b1011, 5).unwrap(), None);
+    assert!(br.find_pattern(0, 65).is_err());
+
+    br.seek(80).unwrap();
+    assert_eq!(br.find_pattern(0, 0).unwrap(), Some(80));
+}
+
+#[test]
+fn test_find_any_pattern() {
+    use dungers_bitbuf::{BitWrite, BitWriter};
+
+    let mut buf = [0u8; 32];
+    let mut bw = BitWriter::new(&mut buf);
+    bw.seek(100).unwrap();
+    bw.write_ubit64(0xabc, 12).unwrap();
+    bw.seek(130).unwrap();
+    bw.write_ubit64(0x5f5f, 16).unwrap();
+
+    let br = BitReader::new(&buf);
+    let patterns = [(0x5f5f, 16), (0xabc, 12)];
+    assert_eq!(br.find_any_pattern(&patterns).unwrap(), Some((1, 100)));
+    assert_eq!(br.find_any_pattern(&patterns[..1]).unwrap(), Some((0, 130)));
+    assert_eq!(br.find_any_pattern(&[]).unwrap(), None);
+}
@@ -0,0 +1,162 @@
+#[cfg(feature = "alloc")]
+use dungers_bitbuf::DynBitVec;
+use dungers_bitbuf::{BitRead, BitReader, BitVec, BitWrite, BitWriter, words_for_bits};
+
+const MAX_EDICTS: usize = 1 << 11;
+
+#[test]
+fn test_bitvec_set_clear_test() {
+    let mut bv = BitVec::<MAX_EDICTS, { words_for_bits(MAX_EDICTS) }>::new();
+    assert_eq!(bv.num_bits(), MAX_EDICTS);
+    assert!(bv.is_all_clear());
+
+    bv.set(0);
+    bv.set(63);
+    bv.set(64);
+    bv.set(MAX_EDICTS - 1);
+    assert!(bv.test(0) && bv.test(63) && bv.test(64) && bv.test(MAX_EDICTS - 1));
+    assert!(!bv.test(1));
+    assert_eq!(bv.count_ones(), 4);
+
+    bv.clear(63);
+    assert!(!bv.test(63));
+    assert_eq!(bv.count_ones(), 3);
+
+    bv.set_all();
+    assert_eq!(bv.count_ones(), MAX_EDICTS);
+    bv.clear_all();
+    assert!(bv.is_all_clear());
+}
+
+#[test]
+fn test_bitvec_ops() {
+    let a = BitVec::<128, 2>::from_words([0b1100, 1 << 63]);
+    let b = BitVec::<128, 2>::from_words([0b1010, 1]);
+
+    let mut and = a;
+    and &= &b;
+    assert_eq!(and.as_words(), &[0b1000, 0]);
+
+    let mut or = a;
+    or |= &b;
+    assert_eq!(or.as_words(), &[0b1110, 1 << 63 | 1]);
+
+    let mut xor = a;
+    xor ^= &b;
+    assert_eq!(xor.as_words(), &[0b0110, 1 << 63 | 1]);
+
+    assert_eq!((!a).count_ones(), 128 - 3);
+}
+
+#[test]
+fn test_bitvec_find_next_set_bit() {
+    let mut bv = BitVec::<192, 3>::new();
+    let bits = [3, 64, 65, 127, 191];
+    for bit in bits {
+        bv.set(bit);
+    }
+
+    assert_eq!(bv.find_next_set_bit(0), Some(3));
+    assert_eq!(bv.find_next_set_bit(3), Some(3));
+    assert_eq!(bv.find_next_set_bit(4), Some(64));
+    assert_eq!(bv.find_next_set_bit(128), Some(191));
+    assert_eq!(bv.find_next_set_bit(192), None);
+    assert_eq!(bv.iter_ones().collect::<Vec<_>>(), bits);
+}
+
+#[test]
+fn test_bitvec_read_write() {
+    let mut bv = BitVec::<128, 2>::new();
+    for bit in [0, 5, 64, 99] {
+        bv.set(bit);
+    }
+
+    let mut buf = [0u8; 16];
+    let mut bw = BitWriter::new(&mut buf);
+    bw.write_bool(true).unwrap();
+    bv.write_to(&mut bw, 100).unwrap();
+    assert!(bv.write_to(&mut bw, 129).is_err());
+
+    let mut br = BitReader::new(&buf);
+    br.skip_bits(1).unwrap();
+    let mut read = BitVec::<128, 2>::from_words([u64::MAX; 2]);
+    read.read_from(&mut br, 100).unwrap();
+    assert_eq!(read, bv);
+}
+
+#[test]
+fn test_bitvec_tail() {
+    let mut bv = BitVec::<70, { words_for_bits(70) }>::new();
+    assert_eq!(bv.num_bits(), 70);
+
+    // bits past N never become set.
+    bv.set_all();
+    assert_eq!(bv.count_ones(), 70);
+    assert_eq!(bv.iter_ones().last(), Some(69));
+    bv = !bv;
+    assert!(bv.is_all_clear());
+    bv = !bv;
+    assert_eq!(bv.find_next_set_bit(70), None);
+
+    let mut bv = BitVec::<70, 2>::from_words([0, u64::MAX]);
+    assert_eq!(bv.count_ones(), 6);
+    assert!(bv.read_from(&mut BitReader::new(&[0; 16]), 71).is_err());
+
+    let mut buf = [0u8; 16];
+    assert!(bv.write_to(&mut BitWriter::new(&mut buf), 71).is_err());
+}
+
+#[test]
+#[should_panic = "bit index out of bounds"]
+fn test_bitvec_out_of_bounds() {
+    BitVec::<70, 2>::new().set(70);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_dynbitvec_tail() {
+    let mut bv = DynBitVec::new(70);
+    assert_eq!(bv.as_words().len(), 2);
+
+    // bits past num_bits never become set.
+    bv.set_all();
+    assert_eq!(bv.count_ones(), 70);
+    bv = !bv;
+    assert!(bv.is_all_clear());
+    bv = !bv;
+    assert_eq!(bv.find_next_set_bit(70), None);
+
+    bv.resize(130);
+    assert_eq!(bv.count_ones(), 70);
+    assert!(!bv.test(129));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+#[should_panic = "bit index out of bounds"]
+fn test_dynbitvec_out_of_bounds() {
+    DynBitVec::new(70).set(70);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_dynbitvec_read_write() {
+    let mut bv = DynBitVec::new(77);
+    for bit in [1, 2, 63, 76] {
+        bv.set(bit);
+    }
+
+    let mut other = DynBitVec::new(77);
+    other.set(2);
+    other.set(40);
+    bv ^= &other;
+    assert_eq!(bv.iter_ones().collect::<Vec<_>>(), [1, 40, 63, 76]);
+
+    let mut buf = [0u8; 10];
+    bv.write_to(&mut BitWriter::new(&mut buf)).unwrap();
+
+    let mut read = DynBitVec::default();
+    read.read_from(&mut BitReader::new(&buf), 77).unwrap();
+    assert_eq!(read, bv);
+    assert!(read.read_from(&mut BitReader::new(&buf), 81).is_err());
+}
@@ -1,4 +1,4 @@
-use dungers_bitbuf::BitWriter;
+use dungers_bitbuf::{BitWrite, BitWriter};
 
 #[test]
 fn test_write_ubit64_extra_bits_erasure() {
@@ -48,3 +48,214 @@ fn test_write_ubit64_spanning_blocks() {
     let block2 = u64::from_le_bytes(buf[8..16].try_into().unwrap());
     assert_eq!(block2, 0xa);
 }
+
+#[test]
+fn test_write_ubit64_partial_trailing_block() {
+    // 11 bytes; the second block is only partially backed by the buffer.
+    let mut buf = [0u8; 11];
+    let mut bw = BitWriter::new(&mut buf);
+
+    bw.write_ubit64(0, 60).unwrap();
+    bw.write_ubit64(0xccbbaa0, 28).unwrap();
+    assert!(bw.write_ubit64(0, 1).is_err());
+    assert_eq!(buf[8..], [0xaa, 0xbb, 0xcc]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_io_write_unaligned() {
+    use std::io::Write;
+
+    let mut buf = [0u8; 4];
+    let mut bw = BitWriter::new(&mut buf);
+
+    bw.write_ubit64(0b000, 3).unwrap();
+    // only 3 whole bytes fit after the first 3 bits.
+    assert_eq!(bw.write(&[0xf5, 0x00, 0x3e, 0xff]).unwrap(), 3);
+    assert_eq!(bw.write(&[0xff]).unwrap(), 0);
+    assert_eq!(bw.num_bits_left(), 5);
+    assert_eq!(buf, [0b1010_1000, 0b0000_0111, 0b1111_0000, 0b0000_0001]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_io_write_all_overflow() {
+    use std::io::{ErrorKind, Write};
+
+    let mut buf = [0u8; 2];
+    let mut bw = BitWriter::new(&mut buf);
+
+    bw.write_all(&[0xaa]).unwrap();
+    let err = bw.write_all(&[0xbb, 0xcc]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::WriteZero);
+}
+
+#[test]
+fn test_reserve_fill() {
+    let mut buf = [0xffu8; 4];
+    let mut bw = BitWriter::new(&mut buf);
+
+    bw.write_ubit64(0b1, 1).unwrap();
+    let reservation = bw.reserve(12).unwrap();
+    bw.write_ubit64(0b101, 3).unwrap();
+    assert_eq!(bw.num_bits_written(), 16);
+
+    bw.fill(reservation, 0xabc).unwrap();
+    assert_eq!(bw.num_bits_written(), 16);
+    assert_eq!(
+        u16::from_le_bytes([buf[0], buf[1]]),
+        0b101 << 13 | 0xabc << 1 | 0b1
+    );
+}
+
+#[test]
+fn test_fill_overflow() {
+    let mut buf = [0u8; 4];
+    let mut bw = BitWriter::new(&mut buf);
+
+    let reservation = bw.reserve(4).unwrap();
+    assert!(bw.fill(reservation, 0x10).is_err());
+}
+
+#[test]
+fn test_write_bit_length_prefixed() {
+    use dungers_bitbuf::{BitRead, BitReader};
+
+    let mut buf = [0u8; 8];
+    let mut bw = BitWriter::new(&mut buf);
+
+    let len = bw
+        .write_bit_length_prefixed(8, |bw| {
+            bw.write_ubit64(0b11, 2)?;
+            bw.write_bit_length_prefixed(5, |bw| bw.write_ubit64(0x7f, 7))?;
+            Ok(())
+        })
+        .unwrap();
+    assert_eq!(len, 2 + 5 + 7);
+
+    let mut br = BitReader::new(&buf);
+    assert_eq!(br.read_ubit64(8).unwrap(), 14);
+    assert_eq!(br.read_ubit64(2).unwrap(), 0b11);
+    assert_eq!(br.read_ubit64(5).unwrap(), 7);
+    assert_eq!(br.read_ubit64(7).unwrap(), 0x7f);
+}
+
+#[test]
+fn test_write_byte_length_prefixed() {
+    use dungers_bitbuf::{BitRead, BitReader};
+
+    let mut buf = [0xffu8; 8];
+    let mut bw = BitWriter::new(&mut buf);
+
+    bw.write_ubit64(0b101, 3).unwrap();
+    let len = bw
+        .write_byte_length_prefixed(8, |bw| {
+            bw.write_bytes(&[0xaa, 0xbb])?;
+            bw.write_ubit64(0b1, 1)
+        })
+        .unwrap();
+    assert_eq!(len, 3);
+    assert_eq!(bw.num_bits_written(), 3 + 8 + 24);
+
+    let mut br = BitReader::new(&buf);
+    br.read_ubit64(3).unwrap();
+    assert_eq!(br.read_byte().unwrap(), 3);
+    let mut body = [0u8; 3];
+    br.read_bytes(&mut body).unwrap();
+    assert_eq!(body, [0xaa, 0xbb, 0x01]);
+}
+
+#[test]
+fn test_write_byte_length_prefixed_exact_fit() {
+    use dungers_bitbuf::{BitRead, BitReader};
+
+    // the body ends exactly at the end of the buffer, so there is nothing to pad.
+    let mut buf = [0u8; 16];
+    let mut bw = BitWriter::new(&mut buf);
+    let len = bw
+        .write_byte_length_prefixed(8, |bw| bw.write_bytes(&[0xcc; 15]))
+        .unwrap();
+    assert_eq!(len, 15);
+    assert_eq!(bw.num_bits_left(), 0);
+
+    // zero-width writes and reservations at the very end are fine too.
+    bw.write_ubit64(0, 0).unwrap();
+    let reservation = bw.reserve(0).unwrap();
+    bw.fill(reservation, 0).unwrap();
+
+    let mut br = BitReader::new(&buf);
+    assert_eq!(br.read_byte().unwrap(), 15);
+    assert_eq!(br.read_ubit64(64).unwrap(), u64::from_le_bytes([0xcc; 8]));
+}
+
+fn xorshift(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+#[test]
+fn test_write_ubit64_from_matches_per_call() {
+    let mut state = 0x2545f4914f6cdd1d;
+    let values: Vec<u64> = (0..64).map(|_| xorshift(&mut state)).collect();
+
+    // odd length, so that the last block is partial, and a whole number of blocks, so that a
+    // block loaded at the end would be out of bounds.
+    for size in [523usize, 520] {
+        for num_bits in 0..=64 {
+            for offset in [0, 1, 7, 63, size * 8] {
+                let len = (size * 8 - offset)
+                    .checked_div(num_bits)
+                    .map_or(values.len(), |len| len.min(values.len()));
+                let values = &values[..len];
+
+                // pre-fill to check that bits outside of the written range are preserved.
+                let mut want = vec![0xa5u8; size];
+                let mut got = want.clone();
+
+                let mut bw = BitWriter::new(&mut want);
+                bw.seek(offset).unwrap();
+                for value in values {
+                    bw.write_ubit64(*value, num_bits).unwrap();
+                }
+                let want_bits = bw.num_bits_written();
+
+                let mut bw = BitWriter::new(&mut got);
+                bw.seek(offset).unwrap();
+                bw.write_ubit64_from(values, num_bits).unwrap();
+                assert_eq!(bw.num_bits_written(), want_bits);
+
+                assert_eq!(got, want, "num_bits={num_bits} offset={offset}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_write_u32s_from() {
+    let mut buf = [0u8; 4];
+    let mut bw = BitWriter::new(&mut buf);
+
+    bw.write_u32s_from(&[0b11_1010_1000, 0b00_0000_0001, 0b00_0001_1111], 10)
+        .unwrap();
+    assert!(bw.write_u32s_from(&[0], 33).is_err());
+    assert_eq!(buf, [0b1010_1000, 0b0000_0111, 0b1111_0000, 0b0000_0001]);
+
+    let mut buf = [0u8; 8];
+    let mut bw = BitWriter::new(&mut buf);
+    bw.seek(64).unwrap();
+    bw.write_u32s_from(&[u32::MAX; 3], 0).unwrap();
+    assert_eq!(bw.num_bits_written(), 64);
+}
+
+#[test]
+fn test_write_ubit64_from_overflow() {
+    let mut buf = [0u8; 8];
+    let mut bw = BitWriter::new(&mut buf);
+
+    assert!(bw.write_ubit64_from(&[u64::MAX; 5], 13).is_err());
+    // nothing was written.
+    assert_eq!(bw.num_bits_written(), 0);
+    assert_eq!(buf, [0; 8]);
+}
@@ -0,0 +1,137 @@
+use dungers_bitbuf::{BitRead, BitReader, ChunkedBitReader};
+
+fn split<'a>(buf: &'a [u8], sizes: &[usize]) -> Vec<&'a [u8]> {
+    let mut chunks = Vec::new();
+    let mut rest = buf;
+    for &size in sizes {
+        let (chunk, tail) = rest.split_at(size);
+        chunks.push(chunk);
+        rest = tail;
+    }
+    chunks.push(rest);
+    chunks
+}
+
+#[test]
+fn test_read_ubit64_matches_contiguous() {
+    let buf: Vec<u8> = (0..64u8).map(|i| i.wrapping_mul(0x9d) ^ 0x5a).collect();
+    let chunks = split(&buf, &[1, 0, 3, 9, 2, 0, 17, 1]);
+
+    let widths = [1, 7, 64, 3, 33, 8, 64, 13, 5, 61, 2, 1, 64];
+
+    let mut want = BitReader::new(&buf);
+    let mut got = ChunkedBitReader::new(&chunks);
+    assert_eq!(got.num_bits_left(), want.num_bits_left());
+
+    for width in widths {
+        assert_eq!(
+            got.read_ubit64(width).unwrap(),
+            want.read_ubit64(width).unwrap()
+        );
+        assert_eq!(got.num_bits_read(), want.num_bits_read());
+    }
+}
+
+#[test]
+fn test_read_ubit64_overflow() {
+    let a = [0xff; 3];
+    let b = [0xff; 5];
+    let chunks = [&a[..], &b[..]];
+    let mut br = ChunkedBitReader::new(&chunks);
+
+    assert!(br.read_ubit64(65).is_err());
+    assert_eq!(br.read_ubit64(64).unwrap(), u64::MAX);
+    assert!(br.read_bool().is_err());
+}
+
+#[test]
+fn test_read_bytes_unaligned() {
+    let buf: Vec<u8> = (0..32u8).collect();
+    let chunks = split(&buf, &[3, 1, 7, 6]);
+
+    let mut want = BitReader::new(&buf);
+    let mut got = ChunkedBitReader::new(&chunks);
+    want.read_ubit64(5).unwrap();
+    got.read_ubit64(5).unwrap();
+
+    let mut want_out = [0u8; 20];
+    let mut got_out = [0u8; 20];
+    want.read_bits(&mut want_out, 155).unwrap();
+    got.read_bits(&mut got_out, 155).unwrap();
+    assert_eq!(got_out, want_out);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_read_slice() {
+    use std::borrow::Cow;
+
+    let a = [0xaa, 0xbb];
+    let b = [0xcc, 0xdd];
+    let chunks = [&a[..], &b[..]];
+    let mut br = ChunkedBitReader::new(&chunks);
+
+    assert!(matches!(br.read_slice(1).unwrap(), Cow::Borrowed([0xaa])));
+
+    let slice = br.read_slice(2).unwrap();
+    assert!(matches!(slice, Cow::Owned(_)));
+    assert_eq!(*slice, [0xbb, 0xcc]);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_read_slice_overflow() {
+    let a = [0xaa, 0xbb];
+    let b = [0xcc, 0xdd];
+    let chunks = [&a[..], &b[..]];
+    let mut br = ChunkedBitReader::new(&chunks);
+
+    // must fail before allocating.
+    assert!(matches!(
+        br.read_slice(usize::MAX),
+        Err(dungers_bitbuf::BitError::Overflow)
+    ));
+    br.read_ubit64(3).unwrap();
+    assert!(matches!(
+        br.read_slice(usize::MAX),
+        Err(dungers_bitbuf::BitError::Overflow)
+    ));
+    assert!(matches!(
+        br.read_slice(4),
+        Err(dungers_bitbuf::BitError::Overflow)
+    ));
+    assert_eq!(br.read_slice(3).unwrap().len(), 3);
+}
+
+#[test]
+fn test_seek() {
+    let a = [0x01, 0x02];
+    let b = [0x03];
+    let c = [0x04, 0x05];
+    let chunks = [&a[..], &b[..], &c[..]];
+    let mut br = ChunkedBitReader::new(&chunks);
+
+    br.seek(32).unwrap();
+    assert_eq!(br.read_byte().unwrap(), 0x05);
+    br.seek(8).unwrap();
+    assert_eq!(br.read_byte().unwrap(), 0x02);
+    assert_eq!(br.seek_relative(-16).unwrap(), 0);
+    assert_eq!(br.read_byte().unwrap(), 0x01);
+    assert!(br.seek(41).is_err());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_io_read() {
+    use std::io::Read;
+
+    let a = [0b1010_1000];
+    let b = [0b0000_0111, 0b1111_0000];
+    let chunks = [&a[..], &b[..]];
+    let mut br = ChunkedBitReader::new(&chunks);
+    br.read_ubit64(3).unwrap();
+
+    let mut out = Vec::new();
+    br.read_to_end(&mut out).unwrap();
+    assert_eq!(out, [0xf5, 0x00]);
+}
@@ -0,0 +1,42 @@
+use std::io::{Read, Write};
+
+use dungers_bitbuf::{BitRead, OwnedBitReader};
+
+fn write_temp_file(name: &str, data: &[u8]) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!("dungers_bitbuf_{name}_{}", std::process::id()));
+    let mut file = std::fs::File::create(&path).unwrap();
+    file.write_all(data).unwrap();
+    path
+}
+
+#[test]
+fn test_open_partial_trailing_block() {
+    // 11 bytes; the mapping is not padded to a multiple of 8.
+    let path = write_temp_file("partial", &[0, 0, 0, 0, 0, 0, 0, 0, 0xaa, 0xbb, 0xcc]);
+
+    let mut br = unsafe { OwnedBitReader::open(&path) }.unwrap();
+    assert_eq!(br.num_bits_left(), 88);
+
+    br.read_ubit64(60).unwrap();
+    assert_eq!(br.read_ubit64(28).unwrap(), 0xccbbaa0);
+    assert!(br.read_ubit64(1).is_err());
+
+    drop(br);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_map_io_read() {
+    let path = write_temp_file("io_read", &[0b1010_1000, 0b0000_0111, 0b1111_0000]);
+
+    let file = std::fs::File::open(&path).unwrap();
+    let mut br = unsafe { OwnedBitReader::map(&file) }.unwrap();
+    br.read_ubit64(3).unwrap();
+
+    let mut out = Vec::new();
+    br.read_to_end(&mut out).unwrap();
+    assert_eq!(out, [0xf5, 0x00]);
+
+    drop(br);
+    std::fs::remove_file(path).unwrap();
+}
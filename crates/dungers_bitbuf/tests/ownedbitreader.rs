@@ -0,0 +1,113 @@
+use std::sync::Arc;
+
+use dungers_bitbuf::{BitRead, OwnedBitReader, SharedBitReader};
+
+#[test]
+fn test_read_ubit64() {
+    let mut br = OwnedBitReader::new(vec![0b110_0101, 0xaa, 0xbb]);
+
+    assert_eq!(br.read_ubit64(3).unwrap(), 0b101);
+    assert_eq!(br.read_ubit64(4).unwrap(), 0b1100);
+    assert_eq!(br.num_bits_read(), 7);
+    assert_eq!(br.num_bits_left(), 17);
+}
+
+#[test]
+fn test_from_words() {
+    let words: Box<[u64]> = vec![0xaabb_ccdd_0000_0005, 1 << 63].into();
+    let mut br = OwnedBitReader::from(words);
+
+    assert_eq!(br.num_bits_left(), 128);
+    assert_eq!(br.read_ubit64(3).unwrap(), 0b101);
+    br.seek(32).unwrap();
+    assert_eq!(br.read_u32().unwrap(), 0xaabb_ccdd);
+    br.seek(127).unwrap();
+    assert!(br.read_bool().unwrap());
+
+    let br = OwnedBitReader::from_words(&[0x0102]);
+    assert_eq!(br.get_ref(), &[0x02, 0x01, 0, 0, 0, 0, 0, 0]);
+}
+
+#[test]
+fn test_as_bit_reader() {
+    let mut br = OwnedBitReader::new(vec![0xaa, 0xbb, 0xcc]);
+    br.read_byte().unwrap();
+
+    // views start at the current bit, but do not advance the owned reader.
+    let mut view = br.as_bit_reader();
+    assert_eq!(view.read_byte().unwrap(), 0xbb);
+    assert_eq!(br.num_bits_read(), 8);
+
+    // with_bit_reader does.
+    let byte = br.with_bit_reader(|view| view.read_byte()).unwrap();
+    assert_eq!(byte, 0xbb);
+    assert_eq!(br.num_bits_read(), 16);
+}
+
+#[test]
+fn test_read_aligned_slice() {
+    let mut br = OwnedBitReader::new(vec![0xaa, 0xbb, 0xcc]);
+
+    br.read_byte().unwrap();
+    assert_eq!(br.read_aligned_slice(2).unwrap(), [0xbb, 0xcc]);
+    assert!(br.read_aligned_slice(1).is_err());
+}
+
+#[test]
+fn test_shared_clone() {
+    let br = SharedBitReader::new(Arc::from([0xaa, 0xbb, 0xcc].as_slice()));
+
+    let mut a = br.clone();
+    let mut b = br;
+    assert_eq!(a.read_byte().unwrap(), 0xaa);
+    assert_eq!(a.read_byte().unwrap(), 0xbb);
+    assert_eq!(b.read_byte().unwrap(), 0xaa);
+    assert!(Arc::ptr_eq(a.get_ref(), b.get_ref()));
+}
+
+#[test]
+fn test_shared_send() {
+    let br = SharedBitReader::from(vec![0xaa, 0xbb]);
+
+    let handles: Vec<_> = (0..2)
+        .map(|_| {
+            let mut br = br.clone();
+            std::thread::spawn(move || br.read_ubit64(16).unwrap())
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), 0xbbaa);
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_io_read() {
+    use std::io::Read;
+
+    let mut br = OwnedBitReader::new(vec![0b1010_1000, 0b0000_0111, 0b1111_0000]);
+    br.read_ubit64(3).unwrap();
+
+    let mut out = Vec::new();
+    br.read_to_end(&mut out).unwrap();
+    assert_eq!(out, [0xf5, 0x00]);
+}
+
+#[test]
+#[cfg(feature = "bytes")]
+fn test_bytes() {
+    use dungers_bitbuf::BytesBitReader;
+
+    let data = bytes::Bytes::from_static(&[0xaa, 0xbb, 0xcc, 0xdd]);
+    let mut br = BytesBitReader::from(data.clone());
+    assert_eq!(br.read_byte().unwrap(), 0xaa);
+
+    let slice = br.read_aligned_bytes(2).unwrap();
+    assert_eq!(slice, [0xbb, 0xcc].as_slice());
+    // shares the buffer instead of copying.
+    assert_eq!(slice.as_ptr(), data[1..].as_ptr());
+    assert_eq!(br.num_bits_read(), 24);
+
+    br.read_bool().unwrap();
+    assert!(br.read_aligned_bytes(1).is_err());
+}
@@ -1,4 +1,4 @@
-use dungers_bitbuf::{BitReader, BitWriter};
+use dungers_bitbuf::{BitError, BitRead, BitReader, BitWrite, BitWriter};
 
 // NOTE: tests are stolen from
 // https://github.com/rust-lang/rust/blob/e5b3e68abf170556b9d56c6f9028318e53c9f06b/compiler/rustc_serialize/tests/leb128.rs
@@ -62,3 +62,304 @@ fn test_varint64() {
         assert_eq!(got, *want);
     }
 }
+
+#[test]
+#[cfg(feature = "std")]
+fn test_io_uvarint64_unaligned() {
+    let values = [0, 1, 127, 128, 300, u64::from(u32::MAX), u64::MAX];
+
+    let mut buf = [0u8; 128];
+
+    let mut bw = BitWriter::new(&mut buf);
+    bw.write_ubit64(0b101, 3).unwrap();
+    for x in &values {
+        dungers_varint::write_uvarint64(&mut bw, *x).unwrap();
+    }
+
+    let mut br = BitReader::new(&buf);
+    assert_eq!(br.read_ubit64(3).unwrap(), 0b101);
+    for want in &values {
+        let (got, _) = dungers_varint::read_uvarint64(&mut br).unwrap();
+        assert_eq!(got, *want);
+    }
+}
+
+#[test]
+fn test_uvarint_too_long() {
+    // used to shift past the width of u64 instead of failing.
+    let buf = [0xff; 11];
+    let mut br = BitReader::new(&buf);
+    assert!(matches!(
+        br.read_uvarint::<u64>(),
+        Err(BitError::MalformedVarint)
+    ));
+}
+
+#[test]
+fn test_uvarint_strict() {
+    // canonical encodings are accepted.
+    for buf in [&[0x00][..], &[0xac, 0x02], &[0xff, 0xff, 0xff, 0xff, 0x0f]] {
+        assert_eq!(
+            BitReader::new(buf).read_uvarint32_strict().unwrap(),
+            BitReader::new(buf).read_uvarint32().unwrap()
+        );
+    }
+
+    // overlong zero; lenient reads accept it.
+    let buf = [0x80, 0x00];
+    assert_eq!(BitReader::new(&buf).read_uvarint32().unwrap(), 0);
+    assert!(matches!(
+        BitReader::new(&buf).read_uvarint32_strict(),
+        Err(BitError::NonCanonicalVarint)
+    ));
+
+    // 5th byte carries bits above 32; lenient reads drop them.
+    let buf = [0xff, 0xff, 0xff, 0xff, 0x1f];
+    assert_eq!(BitReader::new(&buf).read_uvarint32().unwrap(), u32::MAX);
+    assert!(matches!(
+        BitReader::new(&buf).read_uvarint32_strict(),
+        Err(BitError::VarintOverflow)
+    ));
+
+    let mut buf = [0xff; 10];
+    buf[9] = 0x01;
+    assert_eq!(
+        BitReader::new(&buf).read_uvarint_strict::<u64>().unwrap(),
+        u64::MAX
+    );
+    buf[9] = 0x02;
+    assert!(matches!(
+        BitReader::new(&buf).read_uvarint_strict::<u64>(),
+        Err(BitError::VarintOverflow)
+    ));
+}
+
+#[test]
+fn test_generic_varint() {
+    let mut buf = [0u8; 64];
+    let mut bw = BitWriter::new(&mut buf);
+    bw.write_bool(true).unwrap();
+    bw.write_varint(u16::MAX).unwrap();
+    bw.write_varint(i128::MIN).unwrap();
+    bw.write_varint(-3i8).unwrap();
+    bw.write_uvarint64(300).unwrap();
+
+    let mut br = BitReader::new(&buf);
+    assert!(br.read_bool().unwrap());
+    assert_eq!(br.read_varint::<u16>().unwrap(), u16::MAX);
+    assert_eq!(br.read_varint::<i128>().unwrap(), i128::MIN);
+    assert_eq!(br.read_varint_strict::<i8>().unwrap(), -3);
+    assert_eq!(br.read_uvarint::<u64>().unwrap(), 300);
+}
+
+#[test]
+fn test_uvarint_signed() {
+    // read_uvarint does not zigzag decode signed types, read_varint does.
+    let buf = [0x01, 0xff, 0xff, 0xff, 0xff, 0x0f];
+    let mut br = BitReader::new(&buf);
+    assert_eq!(br.read_uvarint::<i64>().unwrap(), 1);
+    assert_eq!(br.read_uvarint::<i32>().unwrap(), -1);
+
+    let mut br = BitReader::new(&buf);
+    assert_eq!(br.read_varint::<i64>().unwrap(), -1);
+    assert_eq!(br.read_varint::<i32>().unwrap(), i32::MIN);
+
+    let mut br = BitReader::new(&buf);
+    br.skip_bits(8).unwrap();
+    assert_eq!(br.read_uvarint_strict::<i32>().unwrap(), -1);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_wire() {
+    use dungers_varint::wire::{Tag, WireType};
+
+    let mut buf = [0u8; 64];
+    let mut bw = BitWriter::new(&mut buf);
+    // protobuf fields are not necessarily byte-aligned inside a bit stream.
+    bw.write_ubit64(0b11, 2).unwrap();
+    bw.write_tag(Tag::new(1, WireType::Fixed32)).unwrap();
+    bw.write_u32(7).unwrap();
+    bw.write_tag(Tag::new(2, WireType::LengthDelimited))
+        .unwrap();
+    bw.write_length_delimited(b"testing").unwrap();
+    bw.write_tag(Tag::new(3, WireType::Varint)).unwrap();
+    bw.write_varint(150u32).unwrap();
+
+    let mut br = BitReader::new(&buf);
+    br.skip_bits(2).unwrap();
+    let tag = br.read_tag().unwrap();
+    br.skip_field(tag).unwrap();
+    assert_eq!(
+        br.read_tag().unwrap(),
+        Tag::new(2, WireType::LengthDelimited)
+    );
+    assert_eq!(&*br.read_length_delimited().unwrap(), b"testing");
+    assert_eq!(br.read_tag().unwrap().field_number, 3);
+    assert_eq!(br.read_varint::<u32>().unwrap(), 150);
+}
+
+#[test]
+fn test_packed_varints() {
+    use dungers_varint::wire::{Tag, WireType};
+
+    // the packed repeated field example from the encoding guide.
+    const PACKED: [u8; 8] = [0x22, 0x06, 0x03, 0x8e, 0x02, 0x9e, 0xa7, 0x05];
+
+    let mut br = BitReader::new(&PACKED);
+    assert_eq!(
+        br.read_tag().unwrap(),
+        Tag::new(4, WireType::LengthDelimited)
+    );
+    let values: Vec<u32> = br
+        .read_packed_varints()
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(values, [3, 270, 86942]);
+    assert_eq!(br.num_bits_left(), 0);
+
+    let mut buf = [0u8; 16];
+    let mut bw = BitWriter::new(&mut buf);
+    bw.write_bool(true).unwrap();
+    bw.write_tag(Tag::new(4, WireType::LengthDelimited))
+        .unwrap();
+    bw.write_packed_varints(&[3u32, 270, 86942]).unwrap();
+    bw.write_varint(150u32).unwrap();
+
+    let mut br = BitReader::new(&buf);
+    br.skip_bits(1).unwrap();
+    br.read_tag().unwrap();
+    assert!(
+        br.read_packed_varints::<i64>()
+            .unwrap()
+            .map(Result::unwrap)
+            .eq([-2, 135, 43471])
+    );
+    assert_eq!(br.read_varint::<u32>().unwrap(), 150);
+
+    // the last varint runs past the end of the field.
+    let buf = [0x02, 0x01, 0x80, 0x01];
+    let mut br = BitReader::new(&buf);
+    let mut packed = br.read_packed_varints::<u32>().unwrap();
+    assert_eq!(packed.next().unwrap().unwrap(), 1);
+    assert!(matches!(
+        packed.next(),
+        Some(Err(BitError::MalformedVarint))
+    ));
+    assert!(packed.next().is_none());
+
+    // the length runs past the input.
+    let buf = [0x05, 0x01];
+    assert!(matches!(
+        BitReader::new(&buf).read_packed_varints::<u32>(),
+        Err(BitError::Overflow)
+    ));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_length_delimited_overflow() {
+    let mut buf = [0u8; 16];
+    let mut bw = BitWriter::new(&mut buf);
+    bw.write_bool(true).unwrap();
+    // a length prefix far larger than the input must not be allocated up front.
+    bw.write_varint(u32::MAX).unwrap();
+    bw.write_bytes(b"short").unwrap();
+
+    let mut br = BitReader::new(&buf);
+    br.skip_bits(1).unwrap();
+    assert!(matches!(
+        br.read_length_delimited(),
+        Err(BitError::Overflow)
+    ));
+}
+
+#[test]
+fn test_oversized_lengths() {
+    use dungers_varint::wire::{Tag, WireType};
+
+    // a length of 2^32 + 5 must not be truncated to 5.
+    let mut buf = [0u8; 32];
+    let mut bw = BitWriter::new(&mut buf);
+    bw.write_bool(true).unwrap();
+    bw.write_tag(Tag::new(2, WireType::LengthDelimited))
+        .unwrap();
+    bw.write_varint((1u64 << 32) | 5).unwrap();
+
+    let mut br = BitReader::new(&buf);
+    br.skip_bits(1).unwrap();
+    let tag = br.read_tag().unwrap();
+    let start = br.num_bits_read();
+    #[cfg(feature = "alloc")]
+    {
+        assert!(matches!(
+            br.read_length_delimited(),
+            Err(BitError::VarintOverflow)
+        ));
+        br.seek(start).unwrap();
+    }
+    assert!(matches!(
+        br.read_packed_varints::<u32>(),
+        Err(BitError::VarintOverflow)
+    ));
+    br.seek(start).unwrap();
+    assert!(matches!(br.skip_field(tag), Err(BitError::VarintOverflow)));
+
+    // same for tags.
+    let mut buf = [0u8; 16];
+    BitWriter::new(&mut buf)
+        .write_varint((1u64 << 32) | 0x08)
+        .unwrap();
+    assert!(matches!(
+        BitReader::new(&buf).read_tag(),
+        Err(BitError::InvalidTag)
+    ));
+}
+
+#[test]
+fn test_sleb128() {
+    let values = [0i64, -1, 127, -128, i64::MIN, i64::MAX];
+
+    let mut buf = [0u8; 128];
+    let mut bw = BitWriter::new(&mut buf);
+    bw.write_bool(false).unwrap();
+    for x in values {
+        bw.write_sleb128(x).unwrap();
+        bw.write_sleb128(x as i32).unwrap();
+    }
+
+    let mut br = BitReader::new(&buf);
+    assert!(!br.read_bool().unwrap());
+    for want in values {
+        assert_eq!(br.read_sleb128::<i64>().unwrap(), want);
+        assert_eq!(br.read_sleb128::<i32>().unwrap(), want as i32);
+    }
+
+    // -2 and 127 from the dwarf spec.
+    let mut br = BitReader::new(&[0x7e, 0xff, 0x00]);
+    assert_eq!(br.read_sleb128::<i32>().unwrap(), -2);
+    assert_eq!(br.read_sleb128::<i32>().unwrap(), 127);
+}
+
+#[test]
+fn test_varint_with_len() {
+    let mut buf = [0u8; 64];
+
+    let mut bw = BitWriter::new(&mut buf);
+    bw.write_uvarint32(300).unwrap();
+    bw.write_varint32(-1).unwrap();
+    bw.write_uvarint64(u64::MAX).unwrap();
+    bw.write_varint64(i64::MIN).unwrap();
+
+    let mut br = BitReader::new(&buf);
+    assert_eq!(br.read_uvarint32_with_len().unwrap(), (300, 2));
+    assert_eq!(br.read_varint32_with_len().unwrap(), (-1, 1));
+    assert_eq!(br.read_uvarint64_with_len().unwrap(), (u64::MAX, 10));
+    assert_eq!(br.read_varint64_with_len().unwrap(), (i64::MIN, 10));
+    assert_eq!(br.num_bits_read(), 23 * 8);
+
+    let mut br = BitReader::new(&buf);
+    assert_eq!(br.read_uvarint64().unwrap(), 300);
+    assert_eq!(br.read_varint_strict_with_len::<i32>().unwrap(), (-1, 1));
+}
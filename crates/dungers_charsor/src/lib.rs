@@ -1,3 +1,4 @@
+#![no_std]
 #![forbid(unsafe_code)]
 #![deny(clippy::all)]
 #![deny(unreachable_pub)]
@@ -12,7 +13,7 @@
 
 use core::ops::Range;
 
-/// enhanced version of [`std::str::Chars`].
+/// enhanced version of [`core::str::Chars`].
 #[derive(Debug, Clone)]
 pub struct Charsor<'a> {
     input: &'a str,
@@ -81,6 +82,10 @@ impl<'a> Charsor<'a> {
 
 #[cfg(test)]
 mod tests {
+    extern crate std;
+
+    use std::string::ToString;
+
     use super::*;
 
     const INPUT: &str = "こんにちは";
@@ -112,7 +117,7 @@ mod tests {
     #[test]
     fn test_eat_while() {
         const PREFIX: &str = "    ";
-        let input = format!("{PREFIX}{INPUT}");
+        let input = std::format!("{PREFIX}{INPUT}");
         let mut cc = Charsor::new(&input);
         cc.eat_while(char::is_whitespace);
         assert_eq!(cc.offset(), PREFIX.len());
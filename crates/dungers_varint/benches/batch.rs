@@ -0,0 +1,52 @@
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use dungers_varint::{batch, streamvbyte};
+
+const NUM_VALUES: usize = 4096;
+// max value bits; small values are the common case.
+const WIDTHS: [u32; 3] = [7, 21, 32];
+
+fn values(num_bits: u32) -> Vec<u32> {
+    (0..NUM_VALUES as u64)
+        .map(|i| (i.wrapping_mul(0x9e37_79b9_7f4a_7c15) >> (64 - num_bits)) as u32)
+        .collect()
+}
+
+fn bench_decode(c: &mut Criterion) {
+    let mut out = vec![0u32; NUM_VALUES];
+
+    let mut group = c.benchmark_group("decode");
+    group.throughput(Throughput::Elements(NUM_VALUES as u64));
+    for num_bits in WIDTHS {
+        let values = values(num_bits);
+
+        let mut leb128 = vec![0u8; batch::encoded_len(&values)];
+        batch::encode(&values, &mut leb128).unwrap();
+        group.bench_with_input(
+            BenchmarkId::new("batch_scalar", num_bits),
+            &leb128,
+            |b, buf| b.iter(|| batch::scalar::decode_u32s(black_box(buf), &mut out).unwrap()),
+        );
+        group.bench_with_input(BenchmarkId::new("batch", num_bits), &leb128, |b, buf| {
+            b.iter(|| batch::decode_u32s(black_box(buf), &mut out).unwrap());
+        });
+
+        let mut svb = vec![0u8; streamvbyte::max_encoded_len_u32s(NUM_VALUES)];
+        streamvbyte::encode_u32s(&values, &mut svb).unwrap();
+        group.bench_with_input(
+            BenchmarkId::new("streamvbyte_scalar", num_bits),
+            &svb,
+            |b, buf| {
+                b.iter(|| streamvbyte::scalar::decode_u32s(black_box(buf), &mut out).unwrap());
+            },
+        );
+        group.bench_with_input(BenchmarkId::new("streamvbyte", num_bits), &svb, |b, buf| {
+            b.iter(|| streamvbyte::decode_u32s(black_box(buf), &mut out).unwrap());
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_decode);
+criterion_main!(benches);
@@ -0,0 +1,262 @@
+// the tokio and futures-io apis only differ in the traits they are built on. each module brings
+// `read`, `write` and `flush` helpers for its traits, this fills in the rest.
+macro_rules! impl_async_io {
+    ($read:ident, $write:ident) => {
+        use crate::{
+            MAX_VARINT_LEN, PartialVarint, Varint, VarintError,
+            framing::{DEFAULT_MAX_FRAME_LEN, FrameDecoder, FrameEncoder, Step},
+        };
+
+        // like the blocking apis, retry reads and writes that were interrupted.
+        async fn read_uninterrupted<R: $read + Unpin>(
+            rdr: &mut R,
+            buf: &mut [u8],
+        ) -> io::Result<usize> {
+            loop {
+                match read(rdr, buf).await {
+                    Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                    result => return result,
+                }
+            }
+        }
+
+        async fn write_uninterrupted<W: $write + Unpin>(
+            w: &mut W,
+            buf: &[u8],
+        ) -> io::Result<usize> {
+            loop {
+                match write(w, buf).await {
+                    Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                    result => return result,
+                }
+            }
+        }
+
+        /// reads varints from an async source.
+        ///
+        /// reads are cancellation safe: when a read future is dropped part way through a varint
+        /// the bytes read so far are kept, and the next read continues with them. it has to read
+        /// the same type.
+        #[derive(Debug)]
+        pub struct VarintReader<R> {
+            rdr: R,
+            partial: PartialVarint,
+        }
+
+        impl<R: $read + Unpin> VarintReader<R> {
+            #[must_use]
+            pub fn new(rdr: R) -> Self {
+                Self {
+                    rdr,
+                    partial: PartialVarint::default(),
+                }
+            }
+
+            pub fn get_ref(&self) -> &R {
+                &self.rdr
+            }
+
+            pub fn get_mut(&mut self) -> &mut R {
+                &mut self.rdr
+            }
+
+            pub fn into_inner(self) -> R {
+                self.rdr
+            }
+
+            /// reads a varint encoded `T` and returns it along with the number of bytes read.
+            pub async fn read_varint<T: Varint>(&mut self) -> Result<(T, usize), VarintError> {
+                loop {
+                    if read_uninterrupted(&mut self.rdr, self.partial.next_byte()).await? == 0 {
+                        return Err(VarintError::UnexpectedEof);
+                    }
+                    if let Some(bytes) = self.partial.push(T::MAX_LEN)? {
+                        return T::decode(bytes);
+                    }
+                }
+            }
+
+            pub async fn read_uvarint64(&mut self) -> Result<(u64, usize), VarintError> {
+                self.read_varint().await
+            }
+
+            pub async fn read_varint64(&mut self) -> Result<(i64, usize), VarintError> {
+                self.read_varint().await
+            }
+
+            pub async fn read_uvarint32(&mut self) -> Result<(u32, usize), VarintError> {
+                self.read_varint().await
+            }
+
+            pub async fn read_varint32(&mut self) -> Result<(i32, usize), VarintError> {
+                self.read_varint().await
+            }
+        }
+
+        /// writes `value` varint encoded and returns the number of bytes written.
+        ///
+        /// like `write_all` this is not cancellation safe, use a [`FrameWriter`] where that
+        /// matters.
+        pub async fn write_varint<W: $write + Unpin, T: Varint>(
+            w: &mut W,
+            value: T,
+        ) -> Result<usize, VarintError> {
+            let mut buf = [0u8; MAX_VARINT_LEN];
+            let count = value.encode(&mut buf)?;
+            let mut written = 0;
+            while written < count {
+                match write_uninterrupted(w, &buf[written..count]).await? {
+                    0 => return Err(io::Error::from(io::ErrorKind::WriteZero).into()),
+                    n => written += n,
+                }
+            }
+            Ok(count)
+        }
+
+        pub async fn write_uvarint64<W: $write + Unpin>(
+            w: &mut W,
+            value: u64,
+        ) -> Result<usize, VarintError> {
+            write_varint(w, value).await
+        }
+
+        pub async fn write_varint64<W: $write + Unpin>(
+            w: &mut W,
+            value: i64,
+        ) -> Result<usize, VarintError> {
+            write_varint(w, value).await
+        }
+
+        pub async fn write_uvarint32<W: $write + Unpin>(
+            w: &mut W,
+            value: u32,
+        ) -> Result<usize, VarintError> {
+            write_varint(w, value).await
+        }
+
+        pub async fn write_varint32<W: $write + Unpin>(
+            w: &mut W,
+            value: i32,
+        ) -> Result<usize, VarintError> {
+            write_varint(w, value).await
+        }
+
+        /// reads frames from an async source; see [`crate::framing`].
+        ///
+        /// [`Self::read_frame`] is cancellation safe: a partially read frame is kept and the next
+        /// call picks up where the dropped one left off.
+        #[derive(Debug)]
+        pub struct FrameReader<R> {
+            rdr: R,
+            decoder: FrameDecoder,
+        }
+
+        impl<R: $read + Unpin> FrameReader<R> {
+            #[must_use]
+            pub fn new(rdr: R) -> Self {
+                Self::with_max_frame_len(rdr, DEFAULT_MAX_FRAME_LEN)
+            }
+
+            #[must_use]
+            pub fn with_max_frame_len(rdr: R, max_frame_len: usize) -> Self {
+                Self {
+                    rdr,
+                    decoder: FrameDecoder::new(max_frame_len),
+                }
+            }
+
+            pub fn get_ref(&self) -> &R {
+                &self.rdr
+            }
+
+            pub fn get_mut(&mut self) -> &mut R {
+                &mut self.rdr
+            }
+
+            pub fn into_inner(self) -> R {
+                self.rdr
+            }
+
+            /// reads the next frame and returns its contents, or [`None`] if the source ended
+            /// cleanly between two frames.
+            pub async fn read_frame(&mut self) -> Result<Option<&[u8]>, VarintError> {
+                loop {
+                    let n = read_uninterrupted(&mut self.rdr, self.decoder.unfilled()).await?;
+                    match self.decoder.advance(n)? {
+                        Step::NeedMore => {}
+                        Step::Frame => return Ok(Some(self.decoder.take_frame())),
+                        Step::End => return Ok(None),
+                    }
+                }
+            }
+        }
+
+        /// writes frames to an async destination; see [`crate::framing`].
+        ///
+        /// frames are queued and then written out, so [`Self::write_frame`] and [`Self::flush`]
+        /// are cancellation safe: whatever has not been written when the future is dropped stays
+        /// queued and goes out ahead of the next frame or on [`Self::flush`].
+        #[derive(Debug)]
+        pub struct FrameWriter<W> {
+            w: W,
+            encoder: FrameEncoder,
+        }
+
+        impl<W: $write + Unpin> FrameWriter<W> {
+            #[must_use]
+            pub fn new(w: W) -> Self {
+                Self::with_max_frame_len(w, DEFAULT_MAX_FRAME_LEN)
+            }
+
+            #[must_use]
+            pub fn with_max_frame_len(w: W, max_frame_len: usize) -> Self {
+                Self {
+                    w,
+                    encoder: FrameEncoder::new(max_frame_len),
+                }
+            }
+
+            pub fn get_ref(&self) -> &W {
+                &self.w
+            }
+
+            pub fn get_mut(&mut self) -> &mut W {
+                &mut self.w
+            }
+
+            /// returns the destination, dropping anything that is still queued.
+            pub fn into_inner(self) -> W {
+                self.w
+            }
+
+            /// the number of queued bytes that have not been written yet.
+            #[must_use]
+            pub fn pending(&self) -> usize {
+                self.encoder.unwritten().len()
+            }
+
+            /// queues `frame` and writes out everything that is queued.
+            pub async fn write_frame(&mut self, frame: &[u8]) -> Result<(), VarintError> {
+                self.encoder.push(frame)?;
+                self.write_pending().await
+            }
+
+            /// writes out everything that is queued and flushes the destination.
+            pub async fn flush(&mut self) -> Result<(), VarintError> {
+                self.write_pending().await?;
+                flush(&mut self.w).await?;
+                Ok(())
+            }
+
+            async fn write_pending(&mut self) -> Result<(), VarintError> {
+                while !self.encoder.unwritten().is_empty() {
+                    let n = write_uninterrupted(&mut self.w, self.encoder.unwritten()).await?;
+                    self.encoder.advance(n)?;
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+pub(crate) use impl_async_io;
@@ -0,0 +1,130 @@
+//! LEB128 encoding and decoding of whole slices of integers, as found in packed arrays.
+//!
+//! with the `swar` feature the decoders load 8 bytes at a time and pull every varint that ends
+//! within them out of the single `u64`, falling back to [`Varint::decode`] near the end of
+//! the input and for varints longer than 8 bytes. this pays off for small values, where a single
+//! load covers several varints; the [`scalar`] decoders are always available and give identical
+//! results.
+
+use crate::{Varint, VarintError};
+
+/// the number of bytes [`encode`] writes for `values`.
+#[must_use]
+pub fn encoded_len<T: Varint>(values: &[T]) -> usize {
+    values.iter().map(|value| value.encoded_len()).sum()
+}
+
+/// encodes `values` back to back into the start of `buf` and returns the number of bytes written.
+pub fn encode<T: Varint>(values: &[T], buf: &mut [u8]) -> Result<usize, VarintError> {
+    let mut pos = 0;
+    for &value in values {
+        pos += value.encode(&mut buf[pos..])?;
+    }
+    Ok(pos)
+}
+
+/// decodes `out.len()` values from the start of `buf` and returns the number of bytes consumed.
+pub fn decode_u32s(buf: &[u8], out: &mut [u32]) -> Result<usize, VarintError> {
+    if cfg!(feature = "swar") {
+        decode_swar(buf, out, |value| value as u32)
+    } else {
+        scalar::decode_u32s(buf, out)
+    }
+}
+
+/// decodes `out.len()` values from the start of `buf` and returns the number of bytes consumed.
+pub fn decode_u64s(buf: &[u8], out: &mut [u64]) -> Result<usize, VarintError> {
+    if cfg!(feature = "swar") {
+        decode_swar(buf, out, |value| value)
+    } else {
+        scalar::decode_u64s(buf, out)
+    }
+}
+
+/// decoders that handle one byte at a time.
+pub mod scalar {
+    use crate::{Varint, VarintError};
+
+    pub fn decode_u32s(buf: &[u8], out: &mut [u32]) -> Result<usize, VarintError> {
+        decode(buf, out)
+    }
+
+    pub fn decode_u64s(buf: &[u8], out: &mut [u64]) -> Result<usize, VarintError> {
+        decode(buf, out)
+    }
+
+    pub(super) fn decode<T: Varint>(buf: &[u8], out: &mut [T]) -> Result<usize, VarintError> {
+        let mut pos = 0;
+        for value in out {
+            let (decoded, n) = T::decode(&buf[pos..])?;
+            *value = decoded;
+            pos += n;
+        }
+        Ok(pos)
+    }
+}
+
+fn decode_swar<T: Varint>(
+    buf: &[u8],
+    out: &mut [T],
+    truncate: impl Fn(u64) -> T,
+) -> Result<usize, VarintError> {
+    let mut pos = 0;
+    let mut i = 0;
+    while i < out.len() {
+        let Some(&word) = buf[pos..].first_chunk::<8>() else {
+            break;
+        };
+        let word = u64::from_le_bytes(word);
+
+        if word & 0x8080_8080_8080_8080 == 0
+            && let Some(chunk) = out[i..].first_chunk_mut::<8>()
+        {
+            // 8 single byte varints, the most common case.
+            for (value, byte) in chunk.iter_mut().zip(word.to_le_bytes()) {
+                *value = truncate(u64::from(byte));
+            }
+            i += 8;
+            pos += 8;
+            continue;
+        }
+
+        // the top bit of the last byte of every varint that ends within the word. decoding all of
+        // them off one load keeps the loop from waiting on each varint's length.
+        let mut stops = !word & 0x8080_8080_8080_8080;
+        let mut start = 0;
+        while stops != 0 && i < out.len() {
+            let end = stops.trailing_zeros() + 1;
+            if ((end - start) / 8) as usize > T::MAX_LEN {
+                break;
+            }
+            out[i] = truncate(squeeze(
+                (word >> start) & (u64::MAX >> (64 - (end - start))),
+            ));
+            i += 1;
+            start = end;
+            stops &= stops - 1;
+        }
+        pos += (start / 8) as usize;
+
+        if start == 0 && i < out.len() {
+            // the next varint is longer than 8 bytes or too long for `T`.
+            let (decoded, n) = T::decode(&buf[pos..])?;
+            out[i] = decoded;
+            i += 1;
+            pos += n;
+        }
+    }
+
+    Ok(pos + scalar::decode(&buf[pos..], &mut out[i..])?)
+}
+
+/// packs the 7-bit groups of the (at most 8 byte long) little-endian varint in `bytes` together.
+fn squeeze(bytes: u64) -> u64 {
+    // drop continuation bits, then squeeze pairs of groups into 14 bits, pairs of those into 28
+    // bits and finally 56 bits.
+    let mut x = bytes & 0x7f7f_7f7f_7f7f_7f7f;
+    x = ((x & 0x7f00_7f00_7f00_7f00) >> 1) | (x & 0x007f_007f_007f_007f);
+    x = ((x & 0x3fff_0000_3fff_0000) >> 2) | (x & 0x0000_3fff_0000_3fff);
+    ((x & 0x0fff_ffff_0000_0000) >> 4) | (x & 0x0000_0000_0fff_ffff)
+}
@@ -0,0 +1,92 @@
+//! varint reads and writes on [`bytes::Buf`] and [`bytes::BufMut`].
+
+use ::bytes::{Buf, BufMut};
+
+use crate::{CONTINUE_BIT, MAX_VARINT_LEN, Varint, VarintError};
+
+/// varint reads for any [`Buf`].
+pub trait BufExt: Buf {
+    /// reads a varint encoded `T` and advances past it.
+    ///
+    /// varints within the current chunk are decoded in place and leave the buffer untouched on
+    /// error. a varint that spans chunks is read a byte at a time, so an error can leave it
+    /// partially consumed.
+    fn get_varint<T: Varint>(&mut self) -> Result<T, VarintError> {
+        match T::decode(self.chunk()) {
+            Ok((value, n)) => {
+                self.advance(n);
+                return Ok(value);
+            }
+            Err(VarintError::UnexpectedEof) if self.remaining() > self.chunk().len() => {}
+            Err(err) => return Err(err),
+        }
+
+        let mut buf = [0u8; MAX_VARINT_LEN];
+        for count in 0..T::MAX_LEN {
+            if !self.has_remaining() {
+                return Err(VarintError::UnexpectedEof);
+            }
+            buf[count] = self.get_u8();
+            if (buf[count] & CONTINUE_BIT) == 0 {
+                return T::decode(&buf[..=count]).map(|(value, _)| value);
+            }
+        }
+        Err(VarintError::MalformedVarint)
+    }
+
+    fn get_uvarint64(&mut self) -> Result<u64, VarintError> {
+        self.get_varint()
+    }
+
+    fn get_varint64(&mut self) -> Result<i64, VarintError> {
+        self.get_varint()
+    }
+
+    fn get_uvarint32(&mut self) -> Result<u32, VarintError> {
+        self.get_varint()
+    }
+
+    fn get_varint32(&mut self) -> Result<i32, VarintError> {
+        self.get_varint()
+    }
+}
+
+impl<B: Buf + ?Sized> BufExt for B {}
+
+/// varint writes for any [`BufMut`].
+pub trait BufMutExt: BufMut {
+    /// writes `value` varint encoded.
+    ///
+    /// # Panics
+    ///
+    /// like [`BufMut::put_slice`], panics if `self` does not have enough remaining capacity.
+    fn put_varint<T: Varint>(&mut self, value: T) {
+        let mut buf = [0u8; MAX_VARINT_LEN];
+        let count = value
+            .encode(&mut buf)
+            .unwrap_or_else(|_| unreachable!("MAX_VARINT_LEN fits any varint"));
+        self.put_slice(&buf[..count]);
+    }
+
+    /// see [`Self::put_varint`].
+    fn put_uvarint64(&mut self, value: u64) {
+        self.put_varint(value);
+    }
+
+    /// see [`Self::put_varint`].
+    fn put_varint64(&mut self, value: i64) {
+        self.put_varint(value);
+    }
+
+    /// see [`Self::put_varint`].
+    fn put_uvarint32(&mut self, value: u32) {
+        self.put_varint(value);
+    }
+
+    /// see [`Self::put_varint`].
+    fn put_varint32(&mut self, value: i32) {
+        self.put_varint(value);
+    }
+}
+
+impl<B: BufMut + ?Sized> BufMutExt for B {}
@@ -0,0 +1,344 @@
+//! frames prefixed with their varint encoded length, for sending records over a stream.
+//!
+//! every reader refuses frames longer than a maximum frame length, so that a corrupt or hostile
+//! length prefix cannot make it allocate arbitrary amounts of memory.
+
+#[cfg(feature = "std")]
+use std::{io, vec::Vec};
+
+#[cfg(feature = "std")]
+use crate::PartialVarint;
+use crate::{Varint, VarintError};
+
+/// the maximum frame length used unless another one is given.
+pub const DEFAULT_MAX_FRAME_LEN: usize = 16 << 20;
+
+/// decodes a frame's length prefix, checking it against `max_frame_len`.
+fn decode_prefix(buf: &[u8], max_frame_len: usize) -> Result<(usize, usize), VarintError> {
+    let (len, n) = u64::decode_strict(buf)?;
+    match usize::try_from(len) {
+        Ok(len) if len <= max_frame_len => Ok((len, n)),
+        _ => Err(VarintError::FrameTooLarge),
+    }
+}
+
+/// iterator over the frames in a byte slice. yields the frames' contents without copying and
+/// stops after the first error.
+#[derive(Debug, Clone)]
+pub struct Frames<'a> {
+    buf: &'a [u8],
+    max_frame_len: usize,
+}
+
+impl<'a> Frames<'a> {
+    #[must_use]
+    pub fn new(buf: &'a [u8]) -> Self {
+        Self::with_max_frame_len(buf, DEFAULT_MAX_FRAME_LEN)
+    }
+
+    #[must_use]
+    pub fn with_max_frame_len(buf: &'a [u8], max_frame_len: usize) -> Self {
+        Self { buf, max_frame_len }
+    }
+
+    /// the input that has not been consumed yet.
+    #[must_use]
+    pub fn remaining(&self) -> &'a [u8] {
+        self.buf
+    }
+
+    fn next_frame(&mut self) -> Result<&'a [u8], VarintError> {
+        let (len, n) = decode_prefix(self.buf, self.max_frame_len)?;
+        let frame = self.buf[n..].get(..len).ok_or(VarintError::UnexpectedEof)?;
+        self.buf = &self.buf[n + len..];
+        Ok(frame)
+    }
+}
+
+impl<'a> Iterator for Frames<'a> {
+    type Item = Result<&'a [u8], VarintError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buf.is_empty() {
+            return None;
+        }
+        let frame = self.next_frame();
+        if frame.is_err() {
+            self.buf = &[];
+        }
+        Some(frame)
+    }
+}
+
+/// the state of a partially read frame, shared by the blocking and async frame readers.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub(crate) struct FrameDecoder {
+    max_frame_len: usize,
+    prefix: PartialVarint,
+    // `Some` once the length prefix has been read.
+    frame_len: Option<usize>,
+    buf: Vec<u8>,
+    filled: usize,
+}
+
+/// what [`FrameDecoder::advance`] made of the bytes it was given.
+#[cfg(feature = "std")]
+pub(crate) enum Step {
+    NeedMore,
+    Frame,
+    End,
+}
+
+#[cfg(feature = "std")]
+impl FrameDecoder {
+    pub(crate) fn new(max_frame_len: usize) -> Self {
+        Self {
+            max_frame_len,
+            prefix: PartialVarint::default(),
+            frame_len: None,
+            buf: Vec::new(),
+            filled: 0,
+        }
+    }
+
+    /// where to read the next bytes into. never empty.
+    pub(crate) fn unfilled(&mut self) -> &mut [u8] {
+        match self.frame_len {
+            Some(_) => &mut self.buf[self.filled..],
+            None => self.prefix.next_byte(),
+        }
+    }
+
+    /// records that `n` bytes were read into [`Self::unfilled`], 0 meaning the end of the input.
+    pub(crate) fn advance(&mut self, n: usize) -> Result<Step, VarintError> {
+        if n == 0 {
+            return if self.frame_len.is_none() && self.prefix.is_empty() {
+                Ok(Step::End)
+            } else {
+                Err(VarintError::UnexpectedEof)
+            };
+        }
+
+        let Some(frame_len) = self.frame_len else {
+            let Some(prefix) = self.prefix.push(u64::MAX_LEN)? else {
+                return Ok(Step::NeedMore);
+            };
+            let (frame_len, _) = decode_prefix(prefix, self.max_frame_len)?;
+            self.frame_len = Some(frame_len);
+            self.buf.clear();
+            self.buf.resize(frame_len, 0);
+            self.filled = 0;
+            // empty frames are done without reading any further.
+            return Ok(if frame_len == 0 {
+                Step::Frame
+            } else {
+                Step::NeedMore
+            });
+        };
+
+        self.filled += n;
+        Ok(if self.filled == frame_len {
+            Step::Frame
+        } else {
+            Step::NeedMore
+        })
+    }
+
+    /// the frame that was just completed.
+    pub(crate) fn take_frame(&mut self) -> &[u8] {
+        let frame_len = self.frame_len.take().unwrap_or_default();
+        &self.buf[..frame_len]
+    }
+}
+
+/// frames that have been queued but not written yet, shared by the blocking and async frame
+/// writers.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub(crate) struct FrameEncoder {
+    max_frame_len: usize,
+    pending: Vec<u8>,
+    written: usize,
+}
+
+#[cfg(feature = "std")]
+impl FrameEncoder {
+    pub(crate) fn new(max_frame_len: usize) -> Self {
+        Self {
+            max_frame_len,
+            pending: Vec::new(),
+            written: 0,
+        }
+    }
+
+    pub(crate) fn push(&mut self, frame: &[u8]) -> Result<(), VarintError> {
+        if frame.len() > self.max_frame_len {
+            return Err(VarintError::FrameTooLarge);
+        }
+        let mut prefix = [0u8; u64::MAX_LEN];
+        let n = (frame.len() as u64).encode(&mut prefix)?;
+        self.pending.extend_from_slice(&prefix[..n]);
+        self.pending.extend_from_slice(frame);
+        Ok(())
+    }
+
+    /// the queued bytes that still have to be written.
+    pub(crate) fn unwritten(&self) -> &[u8] {
+        &self.pending[self.written..]
+    }
+
+    /// records that `n` bytes of [`Self::unwritten`] were written.
+    pub(crate) fn advance(&mut self, n: usize) -> Result<(), VarintError> {
+        if n == 0 {
+            return Err(io::Error::from(io::ErrorKind::WriteZero).into());
+        }
+        self.written += n;
+        if self.written == self.pending.len() {
+            self.pending.clear();
+            self.written = 0;
+        }
+        Ok(())
+    }
+}
+
+/// reads frames from an [`io::Read`].
+///
+/// the length prefix is read a byte at a time, so wrap unbuffered sources in an
+/// [`io::BufReader`]. when the source returns [`io::ErrorKind::WouldBlock`] the error is passed on
+/// and the partially read frame is kept, so that calling [`Self::read_frame`] again once the
+/// source is ready picks up where it left off.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct FrameReader<R> {
+    rdr: R,
+    decoder: FrameDecoder,
+}
+
+#[cfg(feature = "std")]
+impl<R: io::Read> FrameReader<R> {
+    #[must_use]
+    pub fn new(rdr: R) -> Self {
+        Self::with_max_frame_len(rdr, DEFAULT_MAX_FRAME_LEN)
+    }
+
+    #[must_use]
+    pub fn with_max_frame_len(rdr: R, max_frame_len: usize) -> Self {
+        Self {
+            rdr,
+            decoder: FrameDecoder::new(max_frame_len),
+        }
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.rdr
+    }
+
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.rdr
+    }
+
+    pub fn into_inner(self) -> R {
+        self.rdr
+    }
+
+    /// reads the next frame and returns its contents, or [`None`] if the source ended cleanly
+    /// between two frames.
+    pub fn read_frame(&mut self) -> Result<Option<&[u8]>, VarintError> {
+        loop {
+            let n = match self.rdr.read(self.decoder.unfilled()) {
+                Ok(n) => n,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err.into()),
+            };
+            match self.decoder.advance(n)? {
+                Step::NeedMore => {}
+                Step::Frame => return Ok(Some(self.decoder.take_frame())),
+                Step::End => return Ok(None),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: io::Read> Iterator for FrameReader<R> {
+    type Item = Result<Vec<u8>, VarintError>;
+
+    /// like [`FrameReader::read_frame`], but copies each frame out.
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_frame()
+            .map(|frame| frame.map(<[u8]>::to_vec))
+            .transpose()
+    }
+}
+
+/// writes frames to an [`io::Write`].
+///
+/// frames are queued and then written out. when the destination returns
+/// [`io::ErrorKind::WouldBlock`] the error is passed on and whatever has not been written stays
+/// queued, it goes out ahead of the next frame or on [`Self::flush`].
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct FrameWriter<W> {
+    w: W,
+    encoder: FrameEncoder,
+}
+
+#[cfg(feature = "std")]
+impl<W: io::Write> FrameWriter<W> {
+    #[must_use]
+    pub fn new(w: W) -> Self {
+        Self::with_max_frame_len(w, DEFAULT_MAX_FRAME_LEN)
+    }
+
+    #[must_use]
+    pub fn with_max_frame_len(w: W, max_frame_len: usize) -> Self {
+        Self {
+            w,
+            encoder: FrameEncoder::new(max_frame_len),
+        }
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.w
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.w
+    }
+
+    /// returns the destination, dropping anything that is still queued.
+    pub fn into_inner(self) -> W {
+        self.w
+    }
+
+    /// the number of queued bytes that have not been written yet.
+    #[must_use]
+    pub fn pending(&self) -> usize {
+        self.encoder.unwritten().len()
+    }
+
+    /// queues `frame` and writes out as much as possible.
+    pub fn write_frame(&mut self, frame: &[u8]) -> Result<(), VarintError> {
+        self.encoder.push(frame)?;
+        self.write_pending()
+    }
+
+    /// writes out everything that is queued and flushes the destination.
+    pub fn flush(&mut self) -> Result<(), VarintError> {
+        self.write_pending()?;
+        self.w.flush()?;
+        Ok(())
+    }
+
+    fn write_pending(&mut self) -> Result<(), VarintError> {
+        while !self.encoder.unwritten().is_empty() {
+            match self.w.write(self.encoder.unwritten()) {
+                Ok(n) => self.encoder.advance(n)?,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Ok(())
+    }
+}
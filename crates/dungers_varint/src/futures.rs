@@ -0,0 +1,20 @@
+//! varint and framing apis for futures-io's [`AsyncRead`] and [`AsyncWrite`].
+
+use core::{future::poll_fn, pin::Pin};
+use std::io;
+
+use futures_io::{AsyncRead, AsyncWrite};
+
+async fn read<R: AsyncRead + Unpin>(rdr: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+    poll_fn(|cx| Pin::new(&mut *rdr).poll_read(cx, buf)).await
+}
+
+async fn write<W: AsyncWrite + Unpin>(w: &mut W, buf: &[u8]) -> io::Result<usize> {
+    poll_fn(|cx| Pin::new(&mut *w).poll_write(cx, buf)).await
+}
+
+async fn flush<W: AsyncWrite + Unpin>(w: &mut W) -> io::Result<()> {
+    poll_fn(|cx| Pin::new(&mut *w).poll_flush(cx)).await
+}
+
+crate::async_io::impl_async_io!(AsyncRead, AsyncWrite);
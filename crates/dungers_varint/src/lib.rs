@@ -1,3 +1,4 @@
+#![no_std]
 #![forbid(unsafe_code)]
 #![deny(clippy::all)]
 #![deny(unreachable_pub)]
@@ -13,17 +14,60 @@
 #![allow(clippy::cast_possible_wrap)]
 #![allow(clippy::cast_sign_loss)]
 
+//! the crate is `no_std`; the `std::io` based apis need the default `std` feature. async
+//! versions for tokio and futures-io live behind the `tokio` and `futures` features, extension
+//! traits for the `bytes` crate behind the `bytes` feature.
+
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "std")]
 use std::io;
 
+#[cfg(any(feature = "tokio", feature = "futures"))]
+mod async_io;
+pub mod batch;
+#[cfg(feature = "bytes")]
+pub mod bytes;
+pub mod framing;
+#[cfg(feature = "futures")]
+pub mod futures;
+pub mod prefix;
+mod sleb128;
+pub mod sqlite;
+pub mod streamvbyte;
+#[cfg(feature = "tokio")]
+pub mod tokio;
+mod varint;
+pub mod vlq;
+pub mod wire;
+
+pub use sleb128::Sleb128;
+pub use varint::{MAX_VARINT_LEN, Varint};
+
 pub const CONTINUE_BIT: u8 = 0x80;
 pub const PAYLOAD_BITS: u8 = 0x7f;
 
 #[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
 pub enum VarintError {
+    #[cfg(feature = "std")]
     #[error(transparent)]
     IoError(#[from] io::Error),
     #[error("malformed varint")]
     MalformedVarint,
+    #[error("unexpected end of input")]
+    UnexpectedEof,
+    #[error("non-canonical varint: encoding is longer than necessary")]
+    NonCanonical,
+    #[error("varint overflows the target type")]
+    Overflow,
+    #[error("buffer too small")]
+    BufferTooSmall,
+    #[error("invalid protobuf tag")]
+    InvalidTag,
+    #[error("frame exceeds the maximum frame length")]
+    FrameTooLarge,
 }
 
 // ZigZag Transform:  Encodes signed integers so that they can be effectively
@@ -49,21 +93,21 @@ pub enum VarintError {
 //        >> encode >>
 //        << decode <<
 #[must_use]
-pub fn zigzag_encode64(n: i64) -> u64 {
+pub const fn zigzag_encode64(n: i64) -> u64 {
     ((n << 1) ^ (n >> 63)) as u64
 }
 #[must_use]
-pub fn zigzag_decode64(n: u64) -> i64 {
+pub const fn zigzag_decode64(n: u64) -> i64 {
     (n >> 1) as i64 ^ -((n & 1) as i64)
 }
 
 #[must_use]
-pub fn zigzag_encode32(n: i32) -> u32 {
+pub const fn zigzag_encode32(n: i32) -> u32 {
     ((n << 1) ^ (n >> 31)) as u32
 }
 
 #[must_use]
-pub fn zigzag_decode32(n: u32) -> i32 {
+pub const fn zigzag_decode32(n: u32) -> i32 {
     (n >> 1) as i32 ^ -((n & 1) as i32)
 }
 
@@ -88,69 +132,275 @@ pub const fn max_varint_size<T>() -> usize {
     (size_of::<T>() * 8).div_ceil(7)
 }
 
-pub fn write_uvarint64<W: io::Write>(mut w: W, mut value: u64) -> Result<usize, VarintError> {
-    let mut buf = [0u8; max_varint_size::<u64>()];
+/// encodes `value` into the start of `buf` and returns the number of bytes used. `buf` must be
+/// large enough.
+const fn encode_uvarint(mut value: u64, buf: &mut [u8]) -> usize {
     let mut count = 0;
-
-    loop {
-        if value < u64::from(CONTINUE_BIT) {
-            *buf.get_mut(count).ok_or(VarintError::MalformedVarint)? = value as u8;
-            count += 1;
-            break;
-        }
-
-        *buf.get_mut(count).ok_or(VarintError::MalformedVarint)? =
-            ((value & u64::from(PAYLOAD_BITS)) | u64::from(CONTINUE_BIT)) as u8;
+    while value >= CONTINUE_BIT as u64 {
+        buf[count] = (value as u8 & PAYLOAD_BITS) | CONTINUE_BIT;
         value >>= 7;
         count += 1;
     }
+    buf[count] = value as u8;
+    count + 1
+}
+
+/// rejects encodings (as delimited by a lenient decode) that are longer than necessary or that
+/// carry payload bits above `bits`.
+fn check_canonical(bytes: &[u8], bits: usize) -> Result<(), VarintError> {
+    let (&last, rest) = bytes.split_last().ok_or(VarintError::UnexpectedEof)?;
+    if last == 0 && !rest.is_empty() {
+        return Err(VarintError::NonCanonical);
+    }
+    let high_bits = u64::from(last).checked_shr((bits - rest.len() * 7) as u32);
+    if high_bits.is_some_and(|high_bits| high_bits != 0) {
+        return Err(VarintError::Overflow);
+    }
+    Ok(())
+}
+
+/// encodes `value` into `buf` and returns the number of bytes written.
+pub const fn encode_uvarint64(value: u64, buf: &mut [u8; max_varint_size::<u64>()]) -> usize {
+    encode_uvarint(value, buf)
+}
+
+pub const fn encode_varint64(value: i64, buf: &mut [u8; max_varint_size::<u64>()]) -> usize {
+    encode_uvarint64(zigzag_encode64(value), buf)
+}
+
+pub const fn encode_uvarint32(value: u32, buf: &mut [u8; max_varint_size::<u32>()]) -> usize {
+    encode_uvarint(value as u64, buf)
+}
+
+pub const fn encode_varint32(value: i32, buf: &mut [u8; max_varint_size::<u32>()]) -> usize {
+    encode_uvarint32(zigzag_encode32(value), buf)
+}
+
+/// returns the number of bytes [`encode_uvarint64`] would use for `value`.
+#[must_use]
+pub const fn encoded_len_u64(value: u64) -> usize {
+    // at least one byte, even for 0.
+    ((u64::BITS - (value | 1).leading_zeros()) as usize).div_ceil(7)
+}
+
+#[must_use]
+pub const fn encoded_len_u32(value: u32) -> usize {
+    encoded_len_u64(value as u64)
+}
+
+/// returns the number of bytes [`encode_varint64`] would use for `value`.
+#[must_use]
+pub const fn encoded_len_i64(value: i64) -> usize {
+    encoded_len_u64(zigzag_encode64(value))
+}
+
+#[must_use]
+pub const fn encoded_len_i32(value: i32) -> usize {
+    encoded_len_u32(zigzag_encode32(value))
+}
 
+/// encodes `value` into a fresh array and returns it along with the number of bytes used. usable
+/// in const contexts:
+///
+/// ```
+/// const HEADER: ([u8; 10], usize) = dungers_varint::encode_to_array(300);
+/// assert_eq!(&HEADER.0[..HEADER.1], [0xac, 0x02]);
+/// ```
+#[must_use]
+pub const fn encode_to_array(value: u64) -> ([u8; max_varint_size::<u64>()], usize) {
+    let mut buf = [0u8; max_varint_size::<u64>()];
+    let len = encode_uvarint64(value, &mut buf);
+    (buf, len)
+}
+
+/// decodes a varint from the start of `buf` and returns it along with the number of bytes it
+/// occupied. returns [`VarintError::UnexpectedEof`] if `buf` ends in the middle of the varint.
+pub fn decode_uvarint64(buf: &[u8]) -> Result<(u64, usize), VarintError> {
+    u64::decode(buf)
+}
+
+pub fn decode_varint64(buf: &[u8]) -> Result<(i64, usize), VarintError> {
+    i64::decode(buf)
+}
+
+pub fn decode_uvarint32(buf: &[u8]) -> Result<(u32, usize), VarintError> {
+    u32::decode(buf)
+}
+
+pub fn decode_varint32(buf: &[u8]) -> Result<(i32, usize), VarintError> {
+    i32::decode(buf)
+}
+
+/// like [`decode_uvarint64`], but only accepts the canonical encoding: returns
+/// [`VarintError::NonCanonical`] for overlong encodings (e.g. `[0x80, 0x00]` for 0) and
+/// [`VarintError::Overflow`] if the last byte carries bits that do not fit into the type.
+pub fn decode_uvarint64_strict(buf: &[u8]) -> Result<(u64, usize), VarintError> {
+    u64::decode_strict(buf)
+}
+
+pub fn decode_varint64_strict(buf: &[u8]) -> Result<(i64, usize), VarintError> {
+    i64::decode_strict(buf)
+}
+
+pub fn decode_uvarint32_strict(buf: &[u8]) -> Result<(u32, usize), VarintError> {
+    u32::decode_strict(buf)
+}
+
+pub fn decode_varint32_strict(buf: &[u8]) -> Result<(i32, usize), VarintError> {
+    i32::decode_strict(buf)
+}
+
+/// writes `value` varint encoded and returns the number of bytes written.
+#[cfg(feature = "std")]
+pub fn write_varint<W: io::Write, T: Varint>(mut w: W, value: T) -> Result<usize, VarintError> {
+    let mut buf = [0u8; MAX_VARINT_LEN];
+    let count = value.encode(&mut buf)?;
     w.write_all(&buf[..count])?;
     Ok(count)
 }
 
+#[cfg(feature = "std")]
+pub fn write_uvarint64<W: io::Write>(w: W, value: u64) -> Result<usize, VarintError> {
+    write_varint(w, value)
+}
+
+#[cfg(feature = "std")]
 pub fn write_varint64<W: io::Write>(w: W, value: i64) -> Result<usize, VarintError> {
-    write_uvarint64(w, zigzag_encode64(value))
+    write_varint(w, value)
 }
 
-fn read_uvarint<R: io::Read, T>(rdr: &mut R) -> Result<(T, usize), VarintError>
-where
-    T: From<u8> + core::ops::BitOrAssign + core::ops::Shl<usize, Output = T>,
-{
-    let mut buf = [0u8; 1];
+#[cfg(feature = "std")]
+pub fn write_uvarint32<W: io::Write>(w: W, value: u32) -> Result<usize, VarintError> {
+    write_varint(w, value)
+}
 
-    // NOTE: small values are more common then large ones, this is a performance win.
-    rdr.read_exact(&mut buf)?;
-    let byte = buf.first().ok_or(VarintError::MalformedVarint)?;
-    if (byte & CONTINUE_BIT) == 0 {
-        return Ok((T::from(*byte), 1));
-    }
+#[cfg(feature = "std")]
+pub fn write_varint32<W: io::Write>(w: W, value: i32) -> Result<usize, VarintError> {
+    write_varint(w, value)
+}
 
-    let mut value = T::from(byte & PAYLOAD_BITS);
-    for count in 1..max_varint_size::<T>() {
-        rdr.read_exact(&mut buf)?;
-        let byte = buf.first().ok_or(VarintError::MalformedVarint)?;
-        value |= (T::from(byte & PAYLOAD_BITS)) << (count * 7);
-        if (byte & CONTINUE_BIT) == 0 {
-            return Ok((value, count + 1));
+/// reads bytes up to and including the one without a continuation bit, but no more than
+/// `max_len`. the returned bytes are meant to be passed to one of the slice decoders.
+#[cfg(feature = "std")]
+fn read_varint_bytes<R: io::Read>(
+    rdr: &mut R,
+    max_len: usize,
+) -> Result<([u8; MAX_VARINT_LEN], usize), VarintError> {
+    let mut buf = [0u8; MAX_VARINT_LEN];
+    for count in 0..max_len {
+        rdr.read_exact(&mut buf[count..=count])?;
+        if (buf[count] & CONTINUE_BIT) == 0 {
+            return Ok((buf, count + 1));
         }
     }
 
     Err(VarintError::MalformedVarint)
 }
 
+/// the bytes of a varint read so far, for readers that can be interrupted between two bytes.
+#[derive(Debug, Default)]
+#[cfg(feature = "std")]
+pub(crate) struct PartialVarint {
+    buf: [u8; MAX_VARINT_LEN],
+    len: usize,
+}
+
+#[cfg(feature = "std")]
+impl PartialVarint {
+    /// where to read the next byte into.
+    pub(crate) fn next_byte(&mut self) -> &mut [u8] {
+        &mut self.buf[self.len..=self.len]
+    }
+
+    /// records that a byte was read into [`Self::next_byte`] and returns the complete varint
+    /// after its last byte. gives up after `max_len` bytes.
+    pub(crate) fn push(&mut self, max_len: usize) -> Result<Option<&[u8]>, VarintError> {
+        self.len += 1;
+        if (self.buf[self.len - 1] & CONTINUE_BIT) == 0 {
+            let len = core::mem::take(&mut self.len);
+            return Ok(Some(&self.buf[..len]));
+        }
+        if self.len >= max_len {
+            self.len = 0;
+            return Err(VarintError::MalformedVarint);
+        }
+        Ok(None)
+    }
+
+    /// whether no bytes have been read yet.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+/// reads a varint encoded `T` and returns it along with the number of bytes read.
+#[cfg(feature = "std")]
+pub fn read_varint<R: io::Read, T: Varint>(rdr: &mut R) -> Result<(T, usize), VarintError> {
+    let (buf, n) = read_varint_bytes(rdr, T::MAX_LEN)?;
+    T::decode(&buf[..n])
+}
+
+/// see [`Varint::decode_strict`].
+#[cfg(feature = "std")]
+pub fn read_varint_strict<R: io::Read, T: Varint>(rdr: &mut R) -> Result<(T, usize), VarintError> {
+    let (buf, n) = read_varint_bytes(rdr, T::MAX_LEN)?;
+    T::decode_strict(&buf[..n])
+}
+
+#[cfg(feature = "std")]
 pub fn read_uvarint64<R: io::Read>(rdr: &mut R) -> Result<(u64, usize), VarintError> {
-    read_uvarint(rdr)
+    read_varint(rdr)
 }
 
+#[cfg(feature = "std")]
 pub fn read_varint64<R: io::Read>(rdr: &mut R) -> Result<(i64, usize), VarintError> {
-    read_uvarint64(rdr).map(|(value, n)| (zigzag_decode64(value), n))
+    read_varint(rdr)
 }
 
+#[cfg(feature = "std")]
 pub fn read_uvarint32<R: io::Read>(rdr: &mut R) -> Result<(u32, usize), VarintError> {
-    read_uvarint(rdr)
+    read_varint(rdr)
 }
 
+#[cfg(feature = "std")]
 pub fn read_varint32<R: io::Read>(rdr: &mut R) -> Result<(i32, usize), VarintError> {
-    read_uvarint32(rdr).map(|(value, n)| (zigzag_decode32(value), n))
+    read_varint(rdr)
+}
+
+/// see [`decode_uvarint64_strict`].
+#[cfg(feature = "std")]
+pub fn read_uvarint64_strict<R: io::Read>(rdr: &mut R) -> Result<(u64, usize), VarintError> {
+    read_varint_strict(rdr)
+}
+
+#[cfg(feature = "std")]
+pub fn read_varint64_strict<R: io::Read>(rdr: &mut R) -> Result<(i64, usize), VarintError> {
+    read_varint_strict(rdr)
+}
+
+#[cfg(feature = "std")]
+pub fn read_uvarint32_strict<R: io::Read>(rdr: &mut R) -> Result<(u32, usize), VarintError> {
+    read_varint_strict(rdr)
+}
+
+#[cfg(feature = "std")]
+pub fn read_varint32_strict<R: io::Read>(rdr: &mut R) -> Result<(i32, usize), VarintError> {
+    read_varint_strict(rdr)
+}
+
+/// writes `value` as signed LEB128 and returns the number of bytes written; see [`Sleb128`].
+#[cfg(feature = "std")]
+pub fn write_sleb128<W: io::Write, T: Sleb128>(mut w: W, value: T) -> Result<usize, VarintError> {
+    let mut buf = [0u8; MAX_VARINT_LEN];
+    let count = value.encode_sleb128(&mut buf)?;
+    w.write_all(&buf[..count])?;
+    Ok(count)
+}
+
+/// reads a signed LEB128 encoded `T` and returns it along with the number of bytes read; see
+/// [`Sleb128`].
+#[cfg(feature = "std")]
+pub fn read_sleb128<R: io::Read, T: Sleb128>(rdr: &mut R) -> Result<(T, usize), VarintError> {
+    let (buf, n) = read_varint_bytes(rdr, T::MAX_LEN)?;
+    T::decode_sleb128(&buf[..n])
 }
@@ -0,0 +1,84 @@
+//! little-endian prefix varint: the number of trailing zeros in the first byte gives the number of
+//! bytes that follow, so the length is known up front and decoding needs no loop. values of up to
+//! 56 bits take `1 + bits / 7` bytes, larger ones a zero byte followed by the full 8 bytes.
+
+#[cfg(feature = "std")]
+use std::io;
+
+use crate::VarintError;
+
+/// the longest encoding of a `u64`, in bytes.
+pub const MAX_LEN: usize = 9;
+
+/// the number of bytes [`encode`] writes for `value`.
+#[must_use]
+pub const fn encoded_len(value: u64) -> usize {
+    let len = crate::vlq::encoded_len(value);
+    if len < MAX_LEN { len } else { MAX_LEN }
+}
+
+/// the total length of an encoding, given its first byte.
+#[must_use]
+pub const fn decoded_len(first: u8) -> usize {
+    first.trailing_zeros() as usize + 1
+}
+
+/// encodes `value` into the start of `buf` and returns the number of bytes written.
+pub fn encode(value: u64, buf: &mut [u8]) -> Result<usize, VarintError> {
+    let len = encoded_len(value);
+    let buf = buf.get_mut(..len).ok_or(VarintError::BufferTooSmall)?;
+    if len == MAX_LEN {
+        buf[0] = 0;
+        buf[1..].copy_from_slice(&value.to_le_bytes());
+    } else {
+        let encoded = (value << len) | (1 << (len - 1));
+        buf.copy_from_slice(&encoded.to_le_bytes()[..len]);
+    }
+    Ok(len)
+}
+
+/// decodes a value from the start of `buf` and returns it along with the number of bytes it
+/// occupied.
+pub fn decode(buf: &[u8]) -> Result<(u64, usize), VarintError> {
+    let &first = buf.first().ok_or(VarintError::UnexpectedEof)?;
+    let len = decoded_len(first);
+    if buf.len() < len {
+        return Err(VarintError::UnexpectedEof);
+    }
+
+    let mut padded = [0u8; MAX_LEN];
+    let block = if let Some(block) = buf.first_chunk::<MAX_LEN>() {
+        block
+    } else {
+        padded[..buf.len()].copy_from_slice(buf);
+        &padded
+    };
+
+    if len == MAX_LEN {
+        let [_, value @ ..] = *block;
+        return Ok((u64::from_le_bytes(value), len));
+    }
+    let [word @ .., _] = *block;
+    let word = u64::from_le_bytes(word);
+    let mask = u64::MAX >> (u64::BITS as usize - len * 8);
+    Ok(((word & mask) >> len, len))
+}
+
+/// writes `value` with [`encode`] and returns the number of bytes written.
+#[cfg(feature = "std")]
+pub fn write<W: io::Write>(mut w: W, value: u64) -> Result<usize, VarintError> {
+    let mut buf = [0u8; MAX_LEN];
+    let count = encode(value, &mut buf)?;
+    w.write_all(&buf[..count])?;
+    Ok(count)
+}
+
+/// reads a value written by [`write()`] and returns it along with the number of bytes read.
+#[cfg(feature = "std")]
+pub fn read<R: io::Read>(rdr: &mut R) -> Result<(u64, usize), VarintError> {
+    let mut buf = [0u8; MAX_LEN];
+    rdr.read_exact(&mut buf[..1])?;
+    let len = decoded_len(buf[0]);
+    rdr.read_exact(&mut buf[1..len])?;
+    decode(&buf[..len])
+}
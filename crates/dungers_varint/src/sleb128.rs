@@ -0,0 +1,81 @@
+// NOTE: ref impl:
+// https://github.com/rust-lang/rust/blob/e5b3e68abf170556b9d56c6f9028318e53c9f06b/compiler/rustc_serialize/src/leb128.rs
+
+use crate::{CONTINUE_BIT, PAYLOAD_BITS, VarintError};
+
+const SIGN_BIT: u8 = 0x40;
+
+/// a signed integer that can be encoded as two's-complement signed LEB128, as used by DWARF and
+/// wasm. unlike [`crate::Varint`]'s zigzag encoding the sign is carried in bit 6 of the last byte.
+pub trait Sleb128: Sized + Copy {
+    /// the longest possible encoding of `Self`, in bytes.
+    const MAX_LEN: usize;
+
+    /// the number of bytes [`Self::encode_sleb128`] writes for `self`.
+    #[must_use]
+    fn sleb128_len(self) -> usize;
+
+    /// encodes `self` into the start of `buf` and returns the number of bytes written.
+    fn encode_sleb128(self, buf: &mut [u8]) -> Result<usize, VarintError>;
+
+    /// decodes a value from the start of `buf` and returns it along with the number of bytes it
+    /// occupied.
+    fn decode_sleb128(buf: &[u8]) -> Result<(Self, usize), VarintError>;
+}
+
+macro_rules! impl_sleb128 {
+    ($($ty:ty),* $(,)?) => {$(
+        impl Sleb128 for $ty {
+            const MAX_LEN: usize = crate::max_varint_size::<$ty>();
+
+            fn sleb128_len(self) -> usize {
+                // the sign bit needs to fit too.
+                let significant_bits = <$ty>::BITS - (self ^ (self >> 1)).leading_zeros() + 1;
+                (significant_bits as usize).div_ceil(7)
+            }
+
+            fn encode_sleb128(self, buf: &mut [u8]) -> Result<usize, VarintError> {
+                let mut value = self;
+                let mut count = 0;
+                loop {
+                    let mut byte = value as u8 & PAYLOAD_BITS;
+                    value >>= 7;
+                    let more = !((value == 0 && (byte & SIGN_BIT) == 0)
+                        || (value == -1 && (byte & SIGN_BIT) != 0));
+                    if more {
+                        byte |= CONTINUE_BIT;
+                    }
+                    *buf.get_mut(count).ok_or(VarintError::BufferTooSmall)? = byte;
+                    count += 1;
+                    if !more {
+                        return Ok(count);
+                    }
+                }
+            }
+
+            fn decode_sleb128(buf: &[u8]) -> Result<(Self, usize), VarintError> {
+                let mut value: $ty = 0;
+                let mut shift = 0;
+                for (count, &byte) in buf.iter().take(Self::MAX_LEN).enumerate() {
+                    value |= ((byte & PAYLOAD_BITS) as $ty) << shift;
+                    shift += 7;
+                    if (byte & CONTINUE_BIT) == 0 {
+                        if shift < <$ty>::BITS && (byte & SIGN_BIT) != 0 {
+                            // sign extend.
+                            value |= -1 << shift;
+                        }
+                        return Ok((value, count + 1));
+                    }
+                }
+
+                if buf.len() < Self::MAX_LEN {
+                    Err(VarintError::UnexpectedEof)
+                } else {
+                    Err(VarintError::MalformedVarint)
+                }
+            }
+        }
+    )*};
+}
+
+impl_sleb128!(i8, i16, i32, i64, i128);
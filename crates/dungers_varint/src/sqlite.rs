@@ -0,0 +1,77 @@
+//! sqlite's big-endian varint: up to 8 bytes carry 7 bits each and have the continuation bit set
+//! on all but the last, a 9th byte carries a full 8 bits. a `u64` never takes more than 9 bytes.
+//!
+//! <https://www.sqlite.org/fileformat2.html#varint>
+
+#[cfg(feature = "std")]
+use std::io;
+
+use crate::{CONTINUE_BIT, PAYLOAD_BITS, VarintError};
+
+/// the longest encoding of a `u64`, in bytes.
+pub const MAX_LEN: usize = 9;
+
+/// the number of bytes [`encode`] writes for `value`.
+#[must_use]
+pub const fn encoded_len(value: u64) -> usize {
+    if value >> 56 != 0 {
+        MAX_LEN
+    } else {
+        crate::vlq::encoded_len(value)
+    }
+}
+
+/// encodes `value` into the start of `buf` and returns the number of bytes written.
+pub fn encode(mut value: u64, buf: &mut [u8]) -> Result<usize, VarintError> {
+    let len = encoded_len(value);
+    if len < MAX_LEN {
+        return crate::vlq::encode(value, buf);
+    }
+
+    let buf = buf.get_mut(..MAX_LEN).ok_or(VarintError::BufferTooSmall)?;
+    buf[MAX_LEN - 1] = value as u8;
+    value >>= 8;
+    for byte in buf[..MAX_LEN - 1].iter_mut().rev() {
+        *byte = (value as u8 & PAYLOAD_BITS) | CONTINUE_BIT;
+        value >>= 7;
+    }
+    Ok(MAX_LEN)
+}
+
+/// decodes a value from the start of `buf` and returns it along with the number of bytes it
+/// occupied.
+pub fn decode(buf: &[u8]) -> Result<(u64, usize), VarintError> {
+    let mut value = 0u64;
+    for (count, &byte) in buf.iter().take(MAX_LEN).enumerate() {
+        if count == MAX_LEN - 1 {
+            return Ok(((value << 8) | u64::from(byte), MAX_LEN));
+        }
+        value = (value << 7) | u64::from(byte & PAYLOAD_BITS);
+        if (byte & CONTINUE_BIT) == 0 {
+            return Ok((value, count + 1));
+        }
+    }
+    Err(VarintError::UnexpectedEof)
+}
+
+/// writes `value` with [`encode`] and returns the number of bytes written.
+#[cfg(feature = "std")]
+pub fn write<W: io::Write>(mut w: W, value: u64) -> Result<usize, VarintError> {
+    let mut buf = [0u8; MAX_LEN];
+    let count = encode(value, &mut buf)?;
+    w.write_all(&buf[..count])?;
+    Ok(count)
+}
+
+/// reads a value written by [`write()`] and returns it along with the number of bytes read.
+#[cfg(feature = "std")]
+pub fn read<R: io::Read>(rdr: &mut R) -> Result<(u64, usize), VarintError> {
+    let mut buf = [0u8; MAX_LEN];
+    for count in 0..MAX_LEN {
+        rdr.read_exact(&mut buf[count..=count])?;
+        if (buf[count] & CONTINUE_BIT) == 0 {
+            return decode(&buf[..=count]);
+        }
+    }
+    decode(&buf)
+}
@@ -0,0 +1,197 @@
+//! stream vbyte: the byte lengths of all values are stored up front as 2-bit (`u32`) or 4-bit
+//! (`u64`) codes in a control stream, followed by the values' significant little-endian bytes in a
+//! data stream. knowing all lengths up front means decoding does not branch on every byte.
+//!
+//! with the `swar` feature the decoders handle a whole control byte (4 `u32`s or 2 `u64`s) at a
+//! time with fixed-width loads from the data stream instead of the shuffle instructions real simd
+//! implementations use, falling back to the [`scalar`] decoders near the end of the input. both
+//! give identical results.
+//!
+//! <https://arxiv.org/abs/1709.08990>
+
+use crate::VarintError;
+
+/// the number of bytes [`encode_u32s`] writes at most for `len` values.
+#[must_use]
+pub const fn max_encoded_len_u32s(len: usize) -> usize {
+    len.div_ceil(<u32 as Lane>::PER_CONTROL) + len * size_of::<u32>()
+}
+
+/// the number of bytes [`encode_u64s`] writes at most for `len` values.
+#[must_use]
+pub const fn max_encoded_len_u64s(len: usize) -> usize {
+    len.div_ceil(<u64 as Lane>::PER_CONTROL) + len * size_of::<u64>()
+}
+
+/// encodes `values` into the start of `buf` and returns the number of bytes written.
+pub fn encode_u32s(values: &[u32], buf: &mut [u8]) -> Result<usize, VarintError> {
+    encode(values, buf)
+}
+
+/// encodes `values` into the start of `buf` and returns the number of bytes written.
+pub fn encode_u64s(values: &[u64], buf: &mut [u8]) -> Result<usize, VarintError> {
+    encode(values, buf)
+}
+
+/// decodes `out.len()` values from the start of `buf` and returns the number of bytes consumed.
+pub fn decode_u32s(buf: &[u8], out: &mut [u32]) -> Result<usize, VarintError> {
+    if cfg!(feature = "swar") {
+        decode_fast(buf, out)
+    } else {
+        scalar::decode_u32s(buf, out)
+    }
+}
+
+/// decodes `out.len()` values from the start of `buf` and returns the number of bytes consumed.
+pub fn decode_u64s(buf: &[u8], out: &mut [u64]) -> Result<usize, VarintError> {
+    if cfg!(feature = "swar") {
+        decode_fast(buf, out)
+    } else {
+        scalar::decode_u64s(buf, out)
+    }
+}
+
+/// decoders that handle one value at a time.
+pub mod scalar {
+    use super::{Lane, split_streams};
+    use crate::VarintError;
+
+    pub fn decode_u32s(buf: &[u8], out: &mut [u32]) -> Result<usize, VarintError> {
+        decode(buf, out)
+    }
+
+    pub fn decode_u64s(buf: &[u8], out: &mut [u64]) -> Result<usize, VarintError> {
+        decode(buf, out)
+    }
+
+    fn decode<T: Lane>(buf: &[u8], out: &mut [T]) -> Result<usize, VarintError> {
+        let (control, data) = split_streams::<T>(buf, out.len())?;
+        let pos = decode_from(control, data, out, 0)?;
+        Ok(control.len() + pos)
+    }
+
+    /// decodes values starting at `out[start]`, whose data starts at `data[0]`. returns the
+    /// number of data bytes consumed.
+    pub(super) fn decode_from<T: Lane>(
+        control: &[u8],
+        data: &[u8],
+        out: &mut [T],
+        start: usize,
+    ) -> Result<usize, VarintError> {
+        let mut pos = 0;
+        for (i, value) in out.iter_mut().enumerate().skip(start) {
+            let len = T::code_len(control[i / T::PER_CONTROL], i % T::PER_CONTROL)?;
+            let bytes = data.get(pos..pos + len).ok_or(VarintError::UnexpectedEof)?;
+            let mut word = 0u64;
+            for (shift, &byte) in bytes.iter().enumerate() {
+                word |= u64::from(byte) << (shift * 8);
+            }
+            *value = T::from_u64(word);
+            pos += len;
+        }
+        Ok(pos)
+    }
+}
+
+/// what differs between the `u32` and `u64` layouts.
+trait Lane: Copy {
+    /// values per control byte.
+    const PER_CONTROL: usize;
+    const CODE_BITS: usize = 8 / Self::PER_CONTROL;
+
+    fn to_u64(self) -> u64;
+
+    /// truncates `value` to `Self`.
+    fn from_u64(value: u64) -> Self;
+
+    /// the number of data bytes of the `index`th value in `control`. 4-bit codes can express
+    /// lengths wider than `Self`; those return [`VarintError::MalformedVarint`].
+    fn code_len(control: u8, index: usize) -> Result<usize, VarintError> {
+        let len =
+            ((control >> (index * Self::CODE_BITS)) & ((1 << Self::CODE_BITS) - 1)) as usize + 1;
+        if len > size_of::<Self>() {
+            return Err(VarintError::MalformedVarint);
+        }
+        Ok(len)
+    }
+}
+
+impl Lane for u32 {
+    const PER_CONTROL: usize = 4;
+
+    fn to_u64(self) -> u64 {
+        u64::from(self)
+    }
+
+    fn from_u64(value: u64) -> Self {
+        value as u32
+    }
+}
+
+impl Lane for u64 {
+    const PER_CONTROL: usize = 2;
+
+    fn to_u64(self) -> u64 {
+        self
+    }
+
+    fn from_u64(value: u64) -> Self {
+        value
+    }
+}
+
+fn split_streams<T: Lane>(buf: &[u8], len: usize) -> Result<(&[u8], &[u8]), VarintError> {
+    buf.split_at_checked(len.div_ceil(T::PER_CONTROL))
+        .ok_or(VarintError::UnexpectedEof)
+}
+
+fn encode<T: Lane>(values: &[T], buf: &mut [u8]) -> Result<usize, VarintError> {
+    let control_len = values.len().div_ceil(T::PER_CONTROL);
+    let (control, data) = buf
+        .split_at_mut_checked(control_len)
+        .ok_or(VarintError::BufferTooSmall)?;
+
+    let mut pos = 0;
+    for (chunk, control) in values.chunks(T::PER_CONTROL).zip(control) {
+        *control = 0;
+        for (i, &value) in chunk.iter().enumerate() {
+            let value = value.to_u64();
+            // at least one byte, even for 0.
+            let len = ((u64::BITS - (value | 1).leading_zeros()) as usize).div_ceil(8);
+            *control |= ((len - 1) << (i * T::CODE_BITS)) as u8;
+            data.get_mut(pos..pos + len)
+                .ok_or(VarintError::BufferTooSmall)?
+                .copy_from_slice(&value.to_le_bytes()[..len]);
+            pos += len;
+        }
+    }
+    Ok(control_len + pos)
+}
+
+/// decodes a control byte's worth of values at a time, as long as 16 bytes of data are left to
+/// load from.
+fn decode_fast<T: Lane>(buf: &[u8], out: &mut [T]) -> Result<usize, VarintError> {
+    let (control, data) = split_streams::<T>(buf, out.len())?;
+    let width = size_of::<T>();
+
+    let mut pos = 0;
+    let mut done = 0;
+    for (chunk, &control) in out.chunks_exact_mut(T::PER_CONTROL).zip(control) {
+        let Some(block) = data[pos..].first_chunk::<16>() else {
+            break;
+        };
+        let mut offset = 0;
+        for (i, value) in chunk.iter_mut().enumerate() {
+            let len = T::code_len(control, i)?;
+            let mut word = [0u8; 8];
+            word[..width].copy_from_slice(&block[offset..offset + width]);
+            *value = T::from_u64(u64::from_le_bytes(word) & (u64::MAX >> (64 - len * 8)));
+            offset += len;
+        }
+        pos += offset;
+        done += T::PER_CONTROL;
+    }
+
+    pos += scalar::decode_from(control, &data[pos..], out, done)?;
+    Ok(control.len() + pos)
+}
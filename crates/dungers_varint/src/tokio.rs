@@ -0,0 +1,19 @@
+//! varint and framing apis for tokio's [`AsyncRead`] and [`AsyncWrite`].
+
+use std::io;
+
+use ::tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+async fn read<R: AsyncRead + Unpin>(rdr: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+    rdr.read(buf).await
+}
+
+async fn write<W: AsyncWrite + Unpin>(w: &mut W, buf: &[u8]) -> io::Result<usize> {
+    w.write(buf).await
+}
+
+async fn flush<W: AsyncWrite + Unpin>(w: &mut W) -> io::Result<()> {
+    w.flush().await
+}
+
+crate::async_io::impl_async_io!(AsyncRead, AsyncWrite);
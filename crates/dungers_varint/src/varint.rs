@@ -0,0 +1,160 @@
+use crate::{CONTINUE_BIT, PAYLOAD_BITS, VarintError, check_canonical};
+
+/// the longest encoding of any [`Varint`] type (a `u128`/`i128`), handy for sizing stack buffers.
+pub const MAX_VARINT_LEN: usize = max_len::<u128>();
+
+const fn max_len<T>() -> usize {
+    crate::max_varint_size::<T>()
+}
+
+/// an integer that can be varint (LEB128) encoded.
+///
+/// implemented for all primitive integers up to 128 bits. signed integers are zigzag encoded, so
+/// that values with a small absolute value have a short encoding.
+pub trait Varint: Sized + Copy {
+    /// the longest possible encoding of `Self`, in bytes.
+    const MAX_LEN: usize;
+
+    /// the unsigned integer of the same width; `Self` for unsigned types.
+    type Unsigned: Varint<Unsigned = Self::Unsigned>;
+
+    /// reinterprets the bits of `value` as `Self`, without zigzag decoding.
+    #[must_use]
+    fn from_unsigned(value: Self::Unsigned) -> Self;
+
+    /// the number of bytes [`Self::encode`] writes for `self`.
+    #[must_use]
+    fn encoded_len(self) -> usize;
+
+    /// encodes `self` into the start of `buf` and returns the number of bytes written. returns
+    /// [`VarintError::BufferTooSmall`] if `buf` is shorter than [`Self::encoded_len`].
+    fn encode(self, buf: &mut [u8]) -> Result<usize, VarintError>;
+
+    /// decodes a value from the start of `buf` and returns it along with the number of bytes it
+    /// occupied. payload bits that do not fit into `Self` are dropped.
+    fn decode(buf: &[u8]) -> Result<(Self, usize), VarintError>;
+
+    /// like [`Self::decode`], but only accepts the canonical encoding; see
+    /// [`crate::decode_uvarint64_strict`].
+    fn decode_strict(buf: &[u8]) -> Result<(Self, usize), VarintError>;
+}
+
+macro_rules! impl_varint_unsigned {
+    ($($ty:ty),* $(,)?) => {$(
+        impl Varint for $ty {
+            const MAX_LEN: usize = max_len::<$ty>();
+
+            type Unsigned = $ty;
+
+            fn from_unsigned(value: Self::Unsigned) -> Self {
+                value
+            }
+
+            fn encoded_len(self) -> usize {
+                // at least one byte, even for 0.
+                ((<$ty>::BITS - (self | 1).leading_zeros()) as usize).div_ceil(7)
+            }
+
+            fn encode(self, buf: &mut [u8]) -> Result<usize, VarintError> {
+                let len = self.encoded_len();
+                let buf = buf.get_mut(..len).ok_or(VarintError::BufferTooSmall)?;
+                let mut value = self;
+                for byte in &mut buf[..len - 1] {
+                    *byte = (value as u8 & PAYLOAD_BITS) | CONTINUE_BIT;
+                    value >>= 7;
+                }
+                buf[len - 1] = value as u8;
+                Ok(len)
+            }
+
+            // when `buf` holds at least `N` bytes the loop runs over a fixed-size array and gets
+            // fully unrolled; shorter inputs are copied into a zero-padded array first. zero
+            // padding terminates the varint, so running into it means the input was truncated.
+            fn decode(buf: &[u8]) -> Result<(Self, usize), VarintError> {
+                const N: usize = max_len::<$ty>();
+
+                // NOTE: small values are more common then large ones, this is a performance win.
+                match buf.first() {
+                    Some(&byte) if (byte & CONTINUE_BIT) == 0 => return Ok((<$ty>::from(byte), 1)),
+                    None => return Err(VarintError::UnexpectedEof),
+                    _ => {}
+                }
+
+                let mut padded = [0u8; N];
+                let block = if let Some(block) = buf.first_chunk::<N>() {
+                    block
+                } else {
+                    padded[..buf.len()].copy_from_slice(buf);
+                    &padded
+                };
+
+                let mut value: $ty = 0;
+                for (count, byte) in block.iter().enumerate() {
+                    value |= <$ty>::from(byte & PAYLOAD_BITS) << (count * 7);
+                    if (byte & CONTINUE_BIT) == 0 {
+                        return if count < buf.len() {
+                            Ok((value, count + 1))
+                        } else {
+                            Err(VarintError::UnexpectedEof)
+                        };
+                    }
+                }
+
+                Err(VarintError::MalformedVarint)
+            }
+
+            fn decode_strict(buf: &[u8]) -> Result<(Self, usize), VarintError> {
+                let (value, n) = Self::decode(buf)?;
+                check_canonical(&buf[..n], <$ty>::BITS as usize)?;
+                Ok((value, n))
+            }
+        }
+    )*};
+}
+
+impl_varint_unsigned!(u8, u16, u32, u64, u128);
+
+macro_rules! impl_varint_signed {
+    ($($ty:ty => $uty:ty),* $(,)?) => {$(
+        impl Varint for $ty {
+            const MAX_LEN: usize = max_len::<$ty>();
+
+            type Unsigned = $uty;
+
+            fn from_unsigned(value: Self::Unsigned) -> Self {
+                value as $ty
+            }
+
+            fn encoded_len(self) -> usize {
+                zigzag_encode!(self, $ty, $uty).encoded_len()
+            }
+
+            fn encode(self, buf: &mut [u8]) -> Result<usize, VarintError> {
+                zigzag_encode!(self, $ty, $uty).encode(buf)
+            }
+
+            fn decode(buf: &[u8]) -> Result<(Self, usize), VarintError> {
+                <$uty>::decode(buf).map(|(value, n)| (zigzag_decode!(value, $ty), n))
+            }
+
+            fn decode_strict(buf: &[u8]) -> Result<(Self, usize), VarintError> {
+                <$uty>::decode_strict(buf).map(|(value, n)| (zigzag_decode!(value, $ty), n))
+            }
+        }
+    )*};
+}
+
+// same as [`crate::zigzag_encode64`] and friends, for any width.
+macro_rules! zigzag_encode {
+    ($n:expr, $ty:ty, $uty:ty) => {
+        (($n << 1) ^ ($n >> (<$ty>::BITS - 1))) as $uty
+    };
+}
+
+macro_rules! zigzag_decode {
+    ($n:expr, $ty:ty) => {
+        ($n >> 1) as $ty ^ -(($n & 1) as $ty)
+    };
+}
+
+impl_varint_signed!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128);
@@ -0,0 +1,130 @@
+//! big-endian variable-length quantities, as used by midi files and git packfiles: the most
+//! significant 7-bit group comes first and every byte but the last has the continuation bit set.
+//!
+//! the `*_offset` functions implement git's variant for `OFS_DELTA` base offsets, which adds one
+//! to the value before each continuation so that every value has exactly one encoding.
+//!
+//! <https://git-scm.com/docs/gitformat-pack#_deltified_representation>
+
+#[cfg(feature = "std")]
+use std::io;
+
+use crate::{CONTINUE_BIT, PAYLOAD_BITS, VarintError};
+
+/// the longest encoding of a `u64`, in bytes.
+pub const MAX_LEN: usize = crate::max_varint_size::<u64>();
+
+/// the number of bytes [`encode`] writes for `value`.
+#[must_use]
+pub const fn encoded_len(value: u64) -> usize {
+    // at least one byte, even for 0.
+    ((u64::BITS - (value | 1).leading_zeros()) as usize).div_ceil(7)
+}
+
+/// encodes `value` into the start of `buf` and returns the number of bytes written.
+pub fn encode(mut value: u64, buf: &mut [u8]) -> Result<usize, VarintError> {
+    let len = encoded_len(value);
+    let buf = buf.get_mut(..len).ok_or(VarintError::BufferTooSmall)?;
+    buf[len - 1] = value as u8 & PAYLOAD_BITS;
+    for byte in buf[..len - 1].iter_mut().rev() {
+        value >>= 7;
+        *byte = (value as u8 & PAYLOAD_BITS) | CONTINUE_BIT;
+    }
+    Ok(len)
+}
+
+/// decodes a value from the start of `buf` and returns it along with the number of bytes it
+/// occupied. returns [`VarintError::Overflow`] if the value does not fit into a `u64`.
+pub fn decode(buf: &[u8]) -> Result<(u64, usize), VarintError> {
+    let mut value = 0u64;
+    for (count, &byte) in buf.iter().take(MAX_LEN).enumerate() {
+        if value >> (u64::BITS - 7) != 0 {
+            return Err(VarintError::Overflow);
+        }
+        value = (value << 7) | u64::from(byte & PAYLOAD_BITS);
+        if (byte & CONTINUE_BIT) == 0 {
+            return Ok((value, count + 1));
+        }
+    }
+
+    if buf.len() < MAX_LEN {
+        Err(VarintError::UnexpectedEof)
+    } else {
+        Err(VarintError::MalformedVarint)
+    }
+}
+
+/// the number of bytes [`encode_offset`] writes for `value`.
+#[must_use]
+pub const fn offset_encoded_len(mut value: u64) -> usize {
+    let mut len = 1;
+    value >>= 7;
+    while value != 0 {
+        value = (value - 1) >> 7;
+        len += 1;
+    }
+    len
+}
+
+/// encodes `value` the way git encodes `OFS_DELTA` offsets and returns the number of bytes
+/// written.
+pub fn encode_offset(mut value: u64, buf: &mut [u8]) -> Result<usize, VarintError> {
+    let len = offset_encoded_len(value);
+    let buf = buf.get_mut(..len).ok_or(VarintError::BufferTooSmall)?;
+    buf[len - 1] = value as u8 & PAYLOAD_BITS;
+    for byte in buf[..len - 1].iter_mut().rev() {
+        value = (value >> 7) - 1;
+        *byte = (value as u8 & PAYLOAD_BITS) | CONTINUE_BIT;
+    }
+    Ok(len)
+}
+
+/// decodes a value written by [`encode_offset`].
+pub fn decode_offset(buf: &[u8]) -> Result<(u64, usize), VarintError> {
+    let (&first, rest) = buf.split_first().ok_or(VarintError::UnexpectedEof)?;
+    let mut value = u64::from(first & PAYLOAD_BITS);
+    let mut byte = first;
+    let mut count = 1;
+    while (byte & CONTINUE_BIT) != 0 {
+        value = value
+            .checked_add(1)
+            .filter(|value| value >> (u64::BITS - 7) == 0)
+            .ok_or(VarintError::Overflow)?;
+        byte = *rest.get(count - 1).ok_or(VarintError::UnexpectedEof)?;
+        value = (value << 7) | u64::from(byte & PAYLOAD_BITS);
+        count += 1;
+    }
+    Ok((value, count))
+}
+
+/// writes `value` with [`encode`] and returns the number of bytes written.
+#[cfg(feature = "std")]
+pub fn write<W: io::Write>(mut w: W, value: u64) -> Result<usize, VarintError> {
+    let mut buf = [0u8; MAX_LEN];
+    let count = encode(value, &mut buf)?;
+    w.write_all(&buf[..count])?;
+    Ok(count)
+}
+
+/// reads a value written by [`write()`] and returns it along with the number of bytes read.
+#[cfg(feature = "std")]
+pub fn read<R: io::Read>(rdr: &mut R) -> Result<(u64, usize), VarintError> {
+    let (buf, n) = crate::read_varint_bytes(rdr, MAX_LEN)?;
+    decode(&buf[..n])
+}
+
+/// writes `value` with [`encode_offset`] and returns the number of bytes written.
+#[cfg(feature = "std")]
+pub fn write_offset<W: io::Write>(mut w: W, value: u64) -> Result<usize, VarintError> {
+    let mut buf = [0u8; MAX_LEN];
+    let count = encode_offset(value, &mut buf)?;
+    w.write_all(&buf[..count])?;
+    Ok(count)
+}
+
+/// reads a value written by [`write_offset`] and returns it along with the number of bytes read.
+#[cfg(feature = "std")]
+pub fn read_offset<R: io::Read>(rdr: &mut R) -> Result<(u64, usize), VarintError> {
+    let (buf, n) = crate::read_varint_bytes(rdr, MAX_LEN)?;
+    decode_offset(&buf[..n])
+}
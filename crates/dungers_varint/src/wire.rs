@@ -0,0 +1,377 @@
+//! protobuf wire format, for picking fields out of messages without generated code.
+//!
+//! <https://protobuf.dev/programming-guides/encoding/>
+
+use core::marker::PhantomData;
+
+use crate::{Varint, VarintError};
+
+/// how a field's value is encoded on the wire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum WireType {
+    Varint = 0,
+    Fixed64 = 1,
+    LengthDelimited = 2,
+    StartGroup = 3,
+    EndGroup = 4,
+    Fixed32 = 5,
+}
+
+impl TryFrom<u8> for WireType {
+    type Error = VarintError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Ok(match value {
+            0 => Self::Varint,
+            1 => Self::Fixed64,
+            2 => Self::LengthDelimited,
+            3 => Self::StartGroup,
+            4 => Self::EndGroup,
+            5 => Self::Fixed32,
+            _ => return Err(VarintError::InvalidTag),
+        })
+    }
+}
+
+/// a field key: field number and wire type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Tag {
+    pub field_number: u32,
+    pub wire_type: WireType,
+}
+
+impl Tag {
+    /// the largest valid field number.
+    pub const MAX_FIELD_NUMBER: u32 = (1 << 29) - 1;
+
+    /// # Panics
+    ///
+    /// panics if `field_number` is 0 or larger than [`Self::MAX_FIELD_NUMBER`].
+    #[must_use]
+    pub const fn new(field_number: u32, wire_type: WireType) -> Self {
+        assert!(
+            field_number != 0 && field_number <= Self::MAX_FIELD_NUMBER,
+            "field number out of range"
+        );
+        Self {
+            field_number,
+            wire_type,
+        }
+    }
+
+    /// splits a decoded tag varint. returns [`VarintError::InvalidTag`] for unknown wire types and
+    /// field number 0.
+    pub fn from_u32(value: u32) -> Result<Self, VarintError> {
+        let wire_type = WireType::try_from((value & 7) as u8)?;
+        let field_number = value >> 3;
+        if field_number == 0 {
+            return Err(VarintError::InvalidTag);
+        }
+        Ok(Self::new(field_number, wire_type))
+    }
+
+    /// returns [`VarintError::InvalidTag`] if the field number is 0 or larger than
+    /// [`Self::MAX_FIELD_NUMBER`], which can only happen if the fields were set directly.
+    pub const fn to_u32(self) -> Result<u32, VarintError> {
+        if self.field_number == 0 || self.field_number > Self::MAX_FIELD_NUMBER {
+            return Err(VarintError::InvalidTag);
+        }
+        Ok((self.field_number << 3) | self.wire_type as u32)
+    }
+}
+
+/// reads wire format fields from a byte slice.
+#[derive(Debug, Clone)]
+pub struct WireReader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> WireReader<'a> {
+    #[must_use]
+    pub fn new(buf: &'a [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    #[must_use]
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    #[must_use]
+    pub fn remaining(&self) -> &'a [u8] {
+        &self.buf[self.pos..]
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.pos == self.buf.len()
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N], VarintError> {
+        let bytes = *self
+            .remaining()
+            .first_chunk::<N>()
+            .ok_or(VarintError::UnexpectedEof)?;
+        self.pos += N;
+        Ok(bytes)
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], VarintError> {
+        let bytes = self
+            .remaining()
+            .get(..len)
+            .ok_or(VarintError::UnexpectedEof)?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    /// reads the next field's tag, or returns [`None`] at the end of the input.
+    pub fn read_tag(&mut self) -> Result<Option<Tag>, VarintError> {
+        if self.is_empty() {
+            return Ok(None);
+        }
+        let value = self.read_varint::<u64>()?;
+        Tag::from_u32(u32::try_from(value).map_err(|_| VarintError::InvalidTag)?).map(Some)
+    }
+
+    pub fn read_varint<T: Varint>(&mut self) -> Result<T, VarintError> {
+        let (value, n) = T::decode(self.remaining())?;
+        self.pos += n;
+        Ok(value)
+    }
+
+    pub fn read_bool(&mut self) -> Result<bool, VarintError> {
+        self.read_varint::<u64>().map(|value| value != 0)
+    }
+
+    pub fn read_fixed32(&mut self) -> Result<u32, VarintError> {
+        self.take_array().map(u32::from_le_bytes)
+    }
+
+    pub fn read_fixed64(&mut self) -> Result<u64, VarintError> {
+        self.take_array().map(u64::from_le_bytes)
+    }
+
+    /// reads a length-delimited value (bytes, string, sub-message or packed repeated field) and
+    /// returns its contents without copying.
+    pub fn read_length_delimited(&mut self) -> Result<&'a [u8], VarintError> {
+        let len = self.read_len()?;
+        self.take(len)
+    }
+
+    /// reads the length of a length-delimited value. lengths are 32-bit; a longer varint is
+    /// rejected rather than truncated.
+    fn read_len(&mut self) -> Result<usize, VarintError> {
+        let len = u32::try_from(self.read_varint::<u64>()?).map_err(|_| VarintError::Overflow)?;
+        Ok(len as usize)
+    }
+
+    /// reads a length-delimited sub-message.
+    pub fn read_message(&mut self) -> Result<WireReader<'a>, VarintError> {
+        self.read_length_delimited().map(WireReader::new)
+    }
+
+    /// reads a packed repeated field of varints.
+    pub fn read_packed_varints<T: Varint>(&mut self) -> Result<PackedVarints<'a, T>, VarintError> {
+        self.read_length_delimited().map(PackedVarints::new)
+    }
+
+    /// reads a packed repeated field of fixed32 values.
+    pub fn read_packed_fixed32(
+        &mut self,
+    ) -> Result<impl Iterator<Item = u32> + use<'a>, VarintError> {
+        let (chunks, []) = self.read_length_delimited()?.as_chunks::<4>() else {
+            return Err(VarintError::UnexpectedEof);
+        };
+        Ok(chunks.iter().copied().map(u32::from_le_bytes))
+    }
+
+    /// reads a packed repeated field of fixed64 values.
+    pub fn read_packed_fixed64(
+        &mut self,
+    ) -> Result<impl Iterator<Item = u64> + use<'a>, VarintError> {
+        let (chunks, []) = self.read_length_delimited()?.as_chunks::<8>() else {
+            return Err(VarintError::UnexpectedEof);
+        };
+        Ok(chunks.iter().copied().map(u64::from_le_bytes))
+    }
+
+    /// skips the value of a field whose tag has just been read. groups are skipped up to and
+    /// including their matching end tag.
+    pub fn skip_field(&mut self, tag: Tag) -> Result<(), VarintError> {
+        match tag.wire_type {
+            WireType::Varint => self.read_varint::<u64>().map(drop),
+            WireType::Fixed64 => self.take_array::<8>().map(drop),
+            WireType::LengthDelimited => self.read_length_delimited().map(drop),
+            WireType::Fixed32 => self.take_array::<4>().map(drop),
+            WireType::StartGroup => {
+                // groups nest; track the depth instead of recursing.
+                let mut depth = 1usize;
+                while depth > 0 {
+                    let inner = self.read_tag()?.ok_or(VarintError::UnexpectedEof)?;
+                    match inner.wire_type {
+                        WireType::StartGroup => depth += 1,
+                        WireType::EndGroup => depth -= 1,
+                        _ => self.skip_field(inner)?,
+                    }
+                }
+                Ok(())
+            }
+            WireType::EndGroup => Err(VarintError::InvalidTag),
+        }
+    }
+}
+
+/// iterator over a packed repeated field of varints; see [`WireReader::read_packed_varints`].
+#[derive(Debug, Clone)]
+pub struct PackedVarints<'a, T> {
+    buf: &'a [u8],
+    _marker: PhantomData<T>,
+}
+
+impl<'a, T> PackedVarints<'a, T> {
+    #[must_use]
+    pub fn new(buf: &'a [u8]) -> Self {
+        Self {
+            buf,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Varint> Iterator for PackedVarints<'_, T> {
+    type Item = Result<T, VarintError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buf.is_empty() {
+            return None;
+        }
+        Some(match T::decode(self.buf) {
+            Ok((value, n)) => {
+                self.buf = &self.buf[n..];
+                Ok(value)
+            }
+            Err(err) => {
+                // stop after the first error.
+                self.buf = &[];
+                Err(err)
+            }
+        })
+    }
+}
+
+/// writes wire format fields into a byte slice.
+#[derive(Debug)]
+pub struct WireWriter<'a> {
+    buf: &'a mut [u8],
+    pos: usize,
+}
+
+impl<'a> WireWriter<'a> {
+    #[must_use]
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    #[must_use]
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// the bytes written so far.
+    #[must_use]
+    pub fn written(&self) -> &[u8] {
+        &self.buf[..self.pos]
+    }
+
+    fn put(&mut self, bytes: &[u8]) -> Result<(), VarintError> {
+        self.buf
+            .get_mut(self.pos..self.pos + bytes.len())
+            .ok_or(VarintError::BufferTooSmall)?
+            .copy_from_slice(bytes);
+        self.pos += bytes.len();
+        Ok(())
+    }
+
+    pub fn write_tag(&mut self, tag: Tag) -> Result<(), VarintError> {
+        self.write_varint(tag.to_u32()?)
+    }
+
+    pub fn write_varint<T: Varint>(&mut self, value: T) -> Result<(), VarintError> {
+        self.pos += value.encode(&mut self.buf[self.pos..])?;
+        Ok(())
+    }
+
+    pub fn write_fixed32(&mut self, value: u32) -> Result<(), VarintError> {
+        self.put(&value.to_le_bytes())
+    }
+
+    pub fn write_fixed64(&mut self, value: u64) -> Result<(), VarintError> {
+        self.put(&value.to_le_bytes())
+    }
+
+    /// writes `bytes` prefixed with their length.
+    pub fn write_length_delimited(&mut self, bytes: &[u8]) -> Result<(), VarintError> {
+        let len = u32::try_from(bytes.len()).map_err(|_| VarintError::Overflow)?;
+        self.write_varint(len)?;
+        self.put(bytes)
+    }
+
+    /// writes a sub-message produced by `f`, prefixed with its length. the length does not need
+    /// to be known up front.
+    pub fn write_message(
+        &mut self,
+        f: impl FnOnce(&mut WireWriter<'_>) -> Result<(), VarintError>,
+    ) -> Result<(), VarintError> {
+        // leave room for the shortest length prefix, so that a message fits whenever it fits with
+        // its actual prefix, then widen the gap once the length is known.
+        let prefix_pos = self.pos;
+        let start = prefix_pos + 1;
+        let body = self
+            .buf
+            .get_mut(start..)
+            .ok_or(VarintError::BufferTooSmall)?;
+        let mut inner = WireWriter::new(body);
+        f(&mut inner)?;
+        let len = inner.pos;
+
+        let len_u32 = u32::try_from(len).map_err(|_| VarintError::Overflow)?;
+        let body_pos = prefix_pos + len_u32.encoded_len();
+        if body_pos + len > self.buf.len() {
+            return Err(VarintError::BufferTooSmall);
+        }
+        self.buf.copy_within(start..start + len, body_pos);
+        len_u32.encode(&mut self.buf[prefix_pos..])?;
+        self.pos = body_pos + len;
+        Ok(())
+    }
+
+    /// writes `values` as a packed repeated field, including the length prefix.
+    pub fn write_packed_varints<T: Varint>(&mut self, values: &[T]) -> Result<(), VarintError> {
+        let len: usize = values.iter().map(|value| value.encoded_len()).sum();
+        self.write_varint(u32::try_from(len).map_err(|_| VarintError::Overflow)?)?;
+        values
+            .iter()
+            .try_for_each(|&value| self.write_varint(value))
+    }
+
+    /// writes `values` as a packed repeated fixed32 field, including the length prefix.
+    pub fn write_packed_fixed32(&mut self, values: &[u32]) -> Result<(), VarintError> {
+        let len = u32::try_from(values.len() * 4).map_err(|_| VarintError::Overflow)?;
+        self.write_varint(len)?;
+        values
+            .iter()
+            .try_for_each(|&value| self.write_fixed32(value))
+    }
+
+    /// writes `values` as a packed repeated fixed64 field, including the length prefix.
+    pub fn write_packed_fixed64(&mut self, values: &[u64]) -> Result<(), VarintError> {
+        let len = u32::try_from(values.len() * 8).map_err(|_| VarintError::Overflow)?;
+        self.write_varint(len)?;
+        values
+            .iter()
+            .try_for_each(|&value| self.write_fixed64(value))
+    }
+}
@@ -0,0 +1,102 @@
+mod common;
+
+use dungers_varint::{Varint, VarintError, batch};
+
+use common::values;
+
+#[test]
+fn test_batch_round_trip_u64() {
+    for len in [0, 1, 7, 8, 9, 1000] {
+        let values = values(len);
+        let mut buf = vec![0u8; batch::encoded_len(&values)];
+        let n = batch::encode(&values, &mut buf).unwrap();
+        assert_eq!(n, buf.len());
+
+        let mut out = vec![0u64; len];
+        assert_eq!(batch::decode_u64s(&buf, &mut out).unwrap(), n);
+        assert_eq!(out, values);
+
+        let mut scalar = vec![0u64; len];
+        assert_eq!(batch::scalar::decode_u64s(&buf, &mut scalar).unwrap(), n);
+        assert_eq!(scalar, values);
+    }
+}
+
+#[test]
+fn test_batch_round_trip_u32() {
+    let values: Vec<u32> = values(1000).into_iter().map(|x| x as u32).collect();
+    let mut buf = vec![0u8; batch::encoded_len(&values)];
+    let n = batch::encode(&values, &mut buf).unwrap();
+
+    let mut out = vec![0u32; values.len()];
+    assert_eq!(batch::decode_u32s(&buf, &mut out).unwrap(), n);
+    assert_eq!(out, values);
+}
+
+#[test]
+fn test_batch_matches_scalar() {
+    // non-canonical and overlong varints must be treated the same by both decoders.
+    let mut buf = Vec::new();
+    buf.extend_from_slice(&[0x80, 0x80, 0x00]);
+    buf.extend_from_slice(&[0xff, 0xff, 0xff, 0xff, 0x7f]);
+    buf.extend_from_slice(&[0xff; 9]);
+    buf.push(0x01);
+    buf.extend_from_slice(&[0x81; 7]);
+    buf.push(0x01);
+
+    let mut out = [0u64; 4];
+    let mut scalar = [0u64; 4];
+    assert_eq!(
+        batch::decode_u64s(&buf, &mut out).unwrap(),
+        batch::scalar::decode_u64s(&buf, &mut scalar).unwrap()
+    );
+    assert_eq!(out, scalar);
+
+    let mut out = [0u32; 2];
+    let mut scalar = [0u32; 2];
+    assert_eq!(
+        batch::decode_u32s(&buf, &mut out).unwrap(),
+        batch::scalar::decode_u32s(&buf, &mut scalar).unwrap()
+    );
+    assert_eq!(out, scalar);
+    assert_eq!(out[1], u32::decode(&buf[3..]).unwrap().0);
+}
+
+#[test]
+fn test_batch_errors() {
+    let values = values(16);
+    let len = batch::encoded_len(&values);
+    let mut buf = vec![0u8; len];
+    assert!(matches!(
+        batch::encode(&values, &mut buf[..len - 1]),
+        Err(VarintError::BufferTooSmall)
+    ));
+    batch::encode(&values, &mut buf).unwrap();
+
+    let mut out = [0u64; 17];
+    assert!(matches!(
+        batch::decode_u64s(&buf, &mut out),
+        Err(VarintError::UnexpectedEof)
+    ));
+
+    // too long for a u32.
+    let mut out = [0u32; 1];
+    assert!(matches!(
+        batch::decode_u32s(&[0x80; 8], &mut out),
+        Err(VarintError::MalformedVarint)
+    ));
+}
+
+#[test]
+fn test_batch_small_values() {
+    // runs of single byte varints, broken up by longer ones.
+    let values: Vec<u32> = (0..100u32)
+        .map(|i| if i % 19 == 0 { i << 20 } else { i })
+        .collect();
+    let mut buf = vec![0u8; batch::encoded_len(&values)];
+    let n = batch::encode(&values, &mut buf).unwrap();
+
+    let mut out = vec![0u32; values.len()];
+    assert_eq!(batch::decode_u32s(&buf, &mut out).unwrap(), n);
+    assert_eq!(out, values);
+}
@@ -0,0 +1,67 @@
+use bytes::{Buf, Bytes, BytesMut};
+use dungers_varint::{
+    VarintError,
+    bytes::{BufExt, BufMutExt},
+};
+
+#[test]
+fn test_round_trip() {
+    let mut buf = BytesMut::new();
+    buf.put_uvarint64(u64::MAX);
+    buf.put_varint64(-2);
+    buf.put_uvarint32(300);
+    buf.put_varint32(i32::MIN);
+    buf.put_varint(1u8);
+    assert_eq!(&buf[10..13], [0x03, 0xac, 0x02]);
+
+    let mut buf = buf.freeze();
+    assert_eq!(buf.get_uvarint64().unwrap(), u64::MAX);
+    assert_eq!(buf.get_varint64().unwrap(), -2);
+    assert_eq!(buf.get_uvarint32().unwrap(), 300);
+    assert_eq!(buf.get_varint32().unwrap(), i32::MIN);
+    assert_eq!(buf.get_varint::<u8>().unwrap(), 1);
+    assert!(!buf.has_remaining());
+}
+
+#[test]
+fn test_across_chunks() {
+    // 300 split over two chunks.
+    let mut buf = Bytes::from_static(&[0x01, 0xac]).chain(Bytes::from_static(&[0x02, 0x7f]));
+    assert_eq!(buf.get_uvarint32().unwrap(), 1);
+    assert_eq!(buf.get_uvarint32().unwrap(), 300);
+    assert_eq!(buf.get_uvarint32().unwrap(), 127);
+    assert!(matches!(
+        buf.get_uvarint32(),
+        Err(VarintError::UnexpectedEof)
+    ));
+}
+
+#[test]
+fn test_errors() {
+    // errors within a chunk do not consume anything.
+    let mut buf = Bytes::from_static(&[0x80, 0x80]);
+    assert!(matches!(
+        buf.get_uvarint64(),
+        Err(VarintError::UnexpectedEof)
+    ));
+    assert_eq!(buf.remaining(), 2);
+
+    let mut buf = Bytes::from_static(&[0x80; 6]);
+    assert!(matches!(
+        buf.get_uvarint32(),
+        Err(VarintError::MalformedVarint)
+    ));
+
+    let mut buf = Bytes::from_static(&[0x80; 3]).chain(Bytes::from_static(&[0x80; 3]));
+    assert!(matches!(
+        buf.get_uvarint32(),
+        Err(VarintError::MalformedVarint)
+    ));
+}
+
+#[test]
+#[should_panic]
+fn test_put_overflow() {
+    let mut buf = [0u8; 1];
+    (&mut buf[..]).put_uvarint64(300);
+}
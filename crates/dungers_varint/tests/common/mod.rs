@@ -0,0 +1,13 @@
+/// values of every encoded length, in an order that is not too regular.
+pub fn values(len: usize) -> Vec<u64> {
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    (0..len)
+        .map(|_| {
+            // xorshift
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state >> (state % 64)
+        })
+        .collect()
+}
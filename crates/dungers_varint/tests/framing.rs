@@ -0,0 +1,176 @@
+use dungers_varint::{VarintError, framing::Frames};
+
+#[test]
+fn test_frames() {
+    let buf = [0x03, b'a', b'b', b'c', 0x00, 0x01, b'd'];
+    let frames: Vec<&[u8]> = Frames::new(&buf).collect::<Result<_, _>>().unwrap();
+    assert_eq!(frames, [&b"abc"[..], b"", b"d"]);
+}
+
+#[test]
+fn test_frames_errors() {
+    let mut frames = Frames::new(&[0x01, b'a', 0x03, b'b']);
+    assert_eq!(frames.next().unwrap().unwrap(), b"a");
+    assert!(matches!(
+        frames.next(),
+        Some(Err(VarintError::UnexpectedEof))
+    ));
+    assert!(frames.next().is_none());
+
+    let mut frames = Frames::with_max_frame_len(&[0x02, b'a', b'b'], 1);
+    assert!(matches!(
+        frames.next(),
+        Some(Err(VarintError::FrameTooLarge))
+    ));
+
+    // an overlong length prefix.
+    let mut frames = Frames::new(&[0x81, 0x00, b'a']);
+    assert!(matches!(
+        frames.next(),
+        Some(Err(VarintError::NonCanonical))
+    ));
+}
+
+#[cfg(feature = "std")]
+mod io {
+    use std::io::{self, Read, Write};
+
+    use dungers_varint::{
+        VarintError,
+        framing::{FrameReader, FrameWriter, Frames},
+    };
+
+    /// hands out at most one byte per call and fails with `WouldBlock` on every other call.
+    struct Trickle<T> {
+        inner: T,
+        block: bool,
+    }
+
+    impl<T> Trickle<T> {
+        fn new(inner: T) -> Self {
+            Self {
+                inner,
+                block: false,
+            }
+        }
+
+        fn would_block(&mut self) -> io::Result<()> {
+            self.block = !self.block;
+            if self.block {
+                Err(io::ErrorKind::WouldBlock.into())
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    impl<T: Read> Read for Trickle<T> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.would_block()?;
+            let len = buf.len().min(1);
+            self.inner.read(&mut buf[..len])
+        }
+    }
+
+    impl<T: Write> Write for Trickle<T> {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.would_block()?;
+            let len = buf.len().min(1);
+            self.inner.write(&buf[..len])
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            self.inner.flush()
+        }
+    }
+
+    fn is_would_block(err: &VarintError) -> bool {
+        matches!(err, VarintError::IoError(err) if err.kind() == io::ErrorKind::WouldBlock)
+    }
+
+    fn frames() -> Vec<Vec<u8>> {
+        vec![b"hello".to_vec(), Vec::new(), vec![0xaa; 300]]
+    }
+
+    #[test]
+    fn test_frame_round_trip() {
+        let mut fw = FrameWriter::new(Vec::new());
+        for frame in frames() {
+            fw.write_frame(&frame).unwrap();
+        }
+        fw.flush().unwrap();
+        let buf = fw.into_inner();
+        assert_eq!(&buf[..6], b"\x05hello");
+
+        let read: Vec<Vec<u8>> = FrameReader::new(&buf[..])
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(read, frames());
+
+        let read: Vec<&[u8]> = Frames::new(&buf).collect::<Result<_, _>>().unwrap();
+        assert_eq!(read, frames());
+    }
+
+    #[test]
+    fn test_frame_reader_resumes() {
+        let mut buf = Vec::new();
+        let mut fw = FrameWriter::new(&mut buf);
+        for frame in frames() {
+            fw.write_frame(&frame).unwrap();
+        }
+
+        let mut fr = FrameReader::new(Trickle::new(&buf[..]));
+        let mut read = Vec::new();
+        loop {
+            match fr.read_frame() {
+                Ok(Some(frame)) => read.push(frame.to_vec()),
+                Ok(None) => break,
+                Err(err) => assert!(is_would_block(&err), "{err}"),
+            }
+        }
+        assert_eq!(read, frames());
+    }
+
+    #[test]
+    fn test_frame_writer_resumes() {
+        let mut fw = FrameWriter::new(Trickle::new(Vec::new()));
+        for frame in frames() {
+            match fw.write_frame(&frame) {
+                Ok(()) => {}
+                Err(err) => assert!(is_would_block(&err), "{err}"),
+            }
+        }
+        assert!(fw.pending() > 0);
+        while let Err(err) = fw.flush() {
+            assert!(is_would_block(&err), "{err}");
+        }
+        assert_eq!(fw.pending(), 0);
+
+        let buf = fw.into_inner().inner;
+        let read: Vec<&[u8]> = Frames::new(&buf).collect::<Result<_, _>>().unwrap();
+        assert_eq!(read, frames());
+    }
+
+    #[test]
+    fn test_frame_errors() {
+        let mut fw = FrameWriter::with_max_frame_len(Vec::new(), 4);
+        assert!(matches!(
+            fw.write_frame(b"hello"),
+            Err(VarintError::FrameTooLarge)
+        ));
+        fw.write_frame(b"hell").unwrap();
+        let buf = fw.into_inner();
+
+        let mut fr = FrameReader::with_max_frame_len(&buf[..], 3);
+        assert!(matches!(fr.read_frame(), Err(VarintError::FrameTooLarge)));
+
+        // eof in the middle of a frame.
+        let mut fr = FrameReader::new(&buf[..3]);
+        assert!(matches!(fr.read_frame(), Err(VarintError::UnexpectedEof)));
+        let mut fr = FrameReader::new(&[0x80][..]);
+        assert!(matches!(fr.read_frame(), Err(VarintError::UnexpectedEof)));
+
+        let mut fr = FrameReader::new(&[0xff; 11][..]);
+        assert!(matches!(fr.read_frame(), Err(VarintError::MalformedVarint)));
+    }
+}
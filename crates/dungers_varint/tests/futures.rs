@@ -0,0 +1,184 @@
+use std::{
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use dungers_varint::{
+    VarintError,
+    futures::{FrameReader, FrameWriter, VarintReader, write_uvarint64},
+};
+use futures::{FutureExt, executor::block_on, io::Cursor};
+
+/// hands out one byte at a time and returns `Pending` on every other poll.
+struct Trickle<'a> {
+    buf: &'a [u8],
+    pending: bool,
+}
+
+impl futures::AsyncRead for Trickle<'_> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        self.pending = !self.pending;
+        if self.pending {
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        let Some((&first, rest)) = self.buf.split_first() else {
+            return Poll::Ready(Ok(0));
+        };
+        buf[0] = first;
+        self.buf = rest;
+        Poll::Ready(Ok(1))
+    }
+}
+
+#[test]
+fn test_varint_round_trip() {
+    let values = [0u64, 1, 127, 128, 300, u64::MAX];
+    let mut buf = Cursor::new(Vec::new());
+    block_on(async {
+        for value in values {
+            write_uvarint64(&mut buf, value).await.unwrap();
+        }
+    });
+
+    let mut rdr = VarintReader::new(Cursor::new(buf.into_inner()));
+    block_on(async {
+        for value in values {
+            assert_eq!(rdr.read_uvarint64().await.unwrap().0, value);
+        }
+        assert!(matches!(
+            rdr.read_uvarint64().await,
+            Err(VarintError::UnexpectedEof)
+        ));
+    });
+}
+
+#[test]
+fn test_cancel_safe() {
+    // every read is dropped the first time it would have to wait.
+    let mut rdr = VarintReader::new(Trickle {
+        buf: &[0xac, 0x02, 0x96, 0x01],
+        pending: false,
+    });
+    let mut values = Vec::new();
+    while values.len() < 2 {
+        if let Some(value) = rdr.read_uvarint32().now_or_never() {
+            values.push(value.unwrap());
+        }
+    }
+    assert_eq!(values, [(300, 2), (150, 2)]);
+
+    let mut fr = FrameReader::new(Trickle {
+        buf: b"\x05hello\x00",
+        pending: false,
+    });
+    let mut frames = Vec::new();
+    loop {
+        match fr.read_frame().now_or_never() {
+            Some(Ok(Some(frame))) => frames.push(frame.to_vec()),
+            Some(Ok(None)) => break,
+            Some(Err(err)) => panic!("{err}"),
+            None => {}
+        }
+    }
+    assert_eq!(frames, [b"hello".to_vec(), Vec::new()]);
+}
+
+#[test]
+fn test_frame_writer() {
+    let mut fw = FrameWriter::with_max_frame_len(Cursor::new(Vec::new()), 8);
+    block_on(async {
+        fw.write_frame(b"hello").await.unwrap();
+        assert!(matches!(
+            fw.write_frame(&[0; 9]).await,
+            Err(VarintError::FrameTooLarge)
+        ));
+        fw.flush().await.unwrap();
+    });
+    assert_eq!(fw.into_inner().into_inner(), b"\x05hello");
+}
+
+/// fails every other read and write with `Interrupted`, like a syscall hit by a signal.
+struct Interrupting {
+    buf: Vec<u8>,
+    pos: usize,
+    interrupt: bool,
+}
+
+impl Interrupting {
+    fn new(buf: &[u8]) -> Self {
+        Self {
+            buf: buf.to_vec(),
+            pos: 0,
+            interrupt: false,
+        }
+    }
+
+    fn interrupt(&mut self) -> bool {
+        self.interrupt = !self.interrupt;
+        self.interrupt
+    }
+}
+
+impl futures::AsyncRead for Interrupting {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        if self.interrupt() {
+            return Poll::Ready(Err(io::ErrorKind::Interrupted.into()));
+        }
+        let Some(&byte) = self.buf.get(self.pos) else {
+            return Poll::Ready(Ok(0));
+        };
+        buf[0] = byte;
+        self.pos += 1;
+        Poll::Ready(Ok(1))
+    }
+}
+
+impl futures::AsyncWrite for Interrupting {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        if self.interrupt() {
+            return Poll::Ready(Err(io::ErrorKind::Interrupted.into()));
+        }
+        self.buf.push(buf[0]);
+        Poll::Ready(Ok(1))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[test]
+fn test_interrupted() {
+    block_on(async {
+        let mut rdr = VarintReader::new(Interrupting::new(&[0xac, 0x02]));
+        assert_eq!(rdr.read_uvarint32().await.unwrap(), (300, 2));
+
+        let mut fr = FrameReader::new(Interrupting::new(b"\x05hello"));
+        assert_eq!(fr.read_frame().await.unwrap(), Some(&b"hello"[..]));
+
+        let mut w = Interrupting::new(&[]);
+        assert_eq!(write_uvarint64(&mut w, 300).await.unwrap(), 2);
+        let mut fw = FrameWriter::new(w);
+        fw.write_frame(b"hello").await.unwrap();
+        fw.flush().await.unwrap();
+        assert_eq!(fw.into_inner().buf, b"\xac\x02\x05hello");
+    });
+}
@@ -0,0 +1,80 @@
+use dungers_varint::{MAX_VARINT_LEN, Varint, VarintError, encode_uvarint64};
+
+fn round_trip<T: Varint + PartialEq + core::fmt::Debug>(values: impl IntoIterator<Item = T>) {
+    for value in values {
+        let mut buf = [0u8; MAX_VARINT_LEN];
+        let n = value.encode(&mut buf).unwrap();
+        assert_eq!(n, value.encoded_len());
+        assert!(n <= T::MAX_LEN);
+        assert_eq!(T::decode(&buf[..n]).unwrap(), (value, n));
+        assert_eq!(T::decode_strict(&buf).unwrap(), (value, n));
+    }
+}
+
+macro_rules! boundaries {
+    ($ty:ty) => {
+        [<$ty>::MIN, <$ty>::MAX, 0, 1, <$ty>::MAX / 2, <$ty>::MAX / 3]
+    };
+    ($ty:ty, signed) => {
+        [<$ty>::MIN, <$ty>::MAX, 0, 1, -1, -64, 64, <$ty>::MIN / 3]
+    };
+}
+
+#[test]
+fn test_round_trip_all_widths() {
+    round_trip(boundaries!(u8));
+    round_trip(boundaries!(u16));
+    round_trip(boundaries!(u32));
+    round_trip(boundaries!(u64));
+    round_trip(boundaries!(u128));
+    round_trip(boundaries!(i8, signed));
+    round_trip(boundaries!(i16, signed));
+    round_trip(boundaries!(i32, signed));
+    round_trip(boundaries!(i64, signed));
+    round_trip(boundaries!(i128, signed));
+
+    round_trip((0..128).map(|shift| 1u128 << shift));
+    round_trip((0..128).map(|shift| (1i128 << shift).wrapping_neg()));
+}
+
+#[test]
+fn test_matches_fixed_width_functions() {
+    // u16 widened to u64 encodes the same.
+    for value in [0u16, 127, 128, 300, u16::MAX] {
+        let mut buf = [0u8; 10];
+        let n = encode_uvarint64(u64::from(value), &mut buf);
+
+        let mut generic = [0u8; 3];
+        assert_eq!(value.encode(&mut generic).unwrap(), n);
+        assert_eq!(generic[..n], buf[..n]);
+    }
+}
+
+#[test]
+fn test_max_len() {
+    assert_eq!(u8::MAX_LEN, 2);
+    assert_eq!(u32::MAX_LEN, 5);
+    assert_eq!(i64::MAX_LEN, 10);
+    assert_eq!(u128::MAX_LEN, MAX_VARINT_LEN);
+    assert_eq!(MAX_VARINT_LEN, 19);
+}
+
+#[test]
+fn test_errors() {
+    let mut buf = [0u8; 2];
+    assert!(matches!(
+        u32::MAX.encode(&mut buf),
+        Err(VarintError::BufferTooSmall)
+    ));
+
+    // 0xff would need 9 bits.
+    assert_eq!(u8::decode(&[0xff, 0x01]).unwrap(), (u8::MAX, 2));
+    assert!(matches!(
+        u8::decode_strict(&[0xff, 0x03]),
+        Err(VarintError::Overflow)
+    ));
+    assert!(matches!(
+        u8::decode(&[0x80, 0x80]),
+        Err(VarintError::MalformedVarint)
+    ));
+}
@@ -0,0 +1,80 @@
+use dungers_varint::{VarintError, prefix};
+
+const VECTORS: [(u64, &[u8]); 7] = [
+    (0, &[0x01]),
+    (1, &[0x03]),
+    (127, &[0xff]),
+    (128, &[0x02, 0x02]),
+    (16383, &[0xfe, 0xff]),
+    (
+        (1 << 56) - 1,
+        &[0x80, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+    ),
+    (
+        u64::MAX,
+        &[0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+    ),
+];
+
+#[test]
+fn test_prefix_known_vectors() {
+    for (value, want) in VECTORS {
+        let mut buf = [0u8; prefix::MAX_LEN];
+        let n = prefix::encode(value, &mut buf).unwrap();
+        assert_eq!(&buf[..n], want);
+        assert_eq!(prefix::encoded_len(value), n);
+        assert_eq!(prefix::decoded_len(want[0]), n);
+        assert_eq!(prefix::decode(want).unwrap(), (value, n));
+    }
+}
+
+#[test]
+fn test_prefix_round_trip() {
+    let values = (0..64)
+        .flat_map(|shift| [1u64 << shift, (1u64 << shift) - 1])
+        .chain((-500i64..500).map(|i| (i as u64).wrapping_mul(0x0012_3457_89ab_cdef)));
+
+    // trailing garbage must not leak into the value.
+    let mut buf = [0xaau8; 16];
+    for value in values {
+        let n = prefix::encode(value, &mut buf).unwrap();
+        assert_eq!(prefix::encoded_len(value), n);
+        assert_eq!(prefix::decode(&buf).unwrap(), (value, n));
+        assert_eq!(prefix::decode(&buf[..n]).unwrap(), (value, n));
+    }
+}
+
+#[test]
+fn test_prefix_errors() {
+    assert!(matches!(
+        prefix::decode(&[]),
+        Err(VarintError::UnexpectedEof)
+    ));
+    assert!(matches!(
+        prefix::decode(&[0x02]),
+        Err(VarintError::UnexpectedEof)
+    ));
+    assert!(matches!(
+        prefix::decode(&[0x00; 8]),
+        Err(VarintError::UnexpectedEof)
+    ));
+    assert!(matches!(
+        prefix::encode(128, &mut [0u8; 1]),
+        Err(VarintError::BufferTooSmall)
+    ));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_prefix_io() {
+    let mut buf = Vec::new();
+    for (value, _) in VECTORS {
+        prefix::write(&mut buf, value).unwrap();
+    }
+
+    let mut rdr = &buf[..];
+    for (value, encoded) in VECTORS {
+        assert_eq!(prefix::read(&mut rdr).unwrap(), (value, encoded.len()));
+    }
+    assert!(rdr.is_empty());
+}
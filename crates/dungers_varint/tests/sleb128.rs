@@ -0,0 +1,122 @@
+use dungers_varint::{MAX_VARINT_LEN, Sleb128, VarintError};
+
+// NOTE: tests are stolen from
+// https://github.com/rust-lang/rust/blob/e5b3e68abf170556b9d56c6f9028318e53c9f06b/compiler/rustc_serialize/tests/leb128.rs
+// and the dwarf 5 spec, figure c.2.
+
+const DWARF_VECTORS: [(i64, &[u8]); 8] = [
+    (2, &[0x02]),
+    (-2, &[0x7e]),
+    (127, &[0xff, 0x00]),
+    (-127, &[0x81, 0x7f]),
+    (128, &[0x80, 0x01]),
+    (-128, &[0x80, 0x7f]),
+    (129, &[0x81, 0x01]),
+    (-129, &[0xff, 0x7e]),
+];
+
+#[test]
+fn test_known_vectors() {
+    for (value, want) in DWARF_VECTORS {
+        let mut buf = [0u8; 10];
+        let n = value.encode_sleb128(&mut buf).unwrap();
+        assert_eq!(&buf[..n], want);
+        assert_eq!(value.sleb128_len(), n);
+        assert_eq!(i64::decode_sleb128(want).unwrap(), (value, n));
+
+        let value = i32::try_from(value).unwrap();
+        assert_eq!(i32::decode_sleb128(want).unwrap(), (value, n));
+    }
+}
+
+#[test]
+fn test_sleb128_i64() {
+    // test 256 evenly spaced values of integer range, integer max value, and some "random"
+    // numbers.
+    let mut values = Vec::new();
+
+    let mut value = i64::MIN;
+    let increment = 1 << (i64::BITS - 8);
+
+    for _ in 0..256 {
+        values.push(value);
+        // the addition in the last loop iteration overflows.
+        value = value.wrapping_add(increment);
+    }
+
+    values.push(i64::MAX);
+
+    values.extend((-500..500).map(|i| (i as i64).wrapping_mul(0x12345789ABCDEFi64)));
+
+    let mut buf = Vec::new();
+    for x in &values {
+        let mut encoded = [0u8; MAX_VARINT_LEN];
+        let n = x.encode_sleb128(&mut encoded).unwrap();
+        assert_eq!(n, x.sleb128_len());
+        buf.extend_from_slice(&encoded[..n]);
+    }
+
+    let mut pos = 0;
+    for want in &values {
+        let (got, n) = i64::decode_sleb128(&buf[pos..]).unwrap();
+        assert_eq!(got, *want);
+        pos += n;
+    }
+    assert_eq!(pos, buf.len());
+}
+
+#[test]
+fn test_sleb128_widths() {
+    for value in [i32::MIN, i32::MAX, 0, -1, 63, -64, 64, -65] {
+        let mut buf = [0u8; 5];
+        let n = value.encode_sleb128(&mut buf).unwrap();
+        assert_eq!(i32::decode_sleb128(&buf[..n]).unwrap(), (value, n));
+    }
+    for value in [i128::MIN, i128::MAX, i8::MIN.into(), i8::MAX.into()] {
+        let mut buf = [0u8; MAX_VARINT_LEN];
+        let n = value.encode_sleb128(&mut buf).unwrap();
+        assert_eq!(n, value.sleb128_len());
+        assert_eq!(i128::decode_sleb128(&buf[..n]).unwrap(), (value, n));
+    }
+    assert_eq!(i128::MIN.sleb128_len(), i128::MAX_LEN);
+}
+
+#[test]
+fn test_sleb128_errors() {
+    assert!(matches!(
+        i64::decode_sleb128(&[0x80]),
+        Err(VarintError::UnexpectedEof)
+    ));
+    assert!(matches!(
+        i32::decode_sleb128(&[0x80; 5]),
+        Err(VarintError::MalformedVarint)
+    ));
+    assert!(matches!(
+        (-129i64).encode_sleb128(&mut [0u8; 1]),
+        Err(VarintError::BufferTooSmall)
+    ));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_sleb128_io() {
+    use dungers_varint::{read_sleb128, write_sleb128};
+
+    let mut buf = Vec::new();
+    for (value, _) in DWARF_VECTORS {
+        write_sleb128(&mut buf, value).unwrap();
+    }
+    let want: Vec<u8> = DWARF_VECTORS
+        .iter()
+        .flat_map(|(_, b)| b.iter().copied())
+        .collect();
+    assert_eq!(buf, want);
+
+    let mut rdr = &buf[..];
+    for (value, encoded) in DWARF_VECTORS {
+        assert_eq!(
+            read_sleb128::<_, i64>(&mut rdr).unwrap(),
+            (value, encoded.len())
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use dungers_varint::{
+    VarintError, decode_uvarint32, decode_uvarint64, decode_varint32, decode_varint64,
+    encode_uvarint32, encode_uvarint64, encode_varint32, encode_varint64,
+};
+
+#[test]
+fn test_uvarint64_round_trip() {
+    let values = (0..64)
+        .map(|shift| 1u64 << shift)
+        .chain((0..64).map(|shift| (1u64 << shift) - 1))
+        .chain([u64::MAX, 300, 0x0012_3457_89ab_cdef]);
+
+    for value in values {
+        let mut buf = [0u8; 10];
+        let n = encode_uvarint64(value, &mut buf);
+        assert_eq!(n, (64 - value.leading_zeros() as usize).div_ceil(7).max(1));
+        assert_eq!(decode_uvarint64(&buf[..n]).unwrap(), (value, n));
+        // trailing bytes are not consumed.
+        assert_eq!(decode_uvarint64(&buf).unwrap(), (value, n));
+    }
+}
+
+#[test]
+fn test_signed_round_trip() {
+    for value in [0, 1, -1, 63, -64, 64, i64::MIN, i64::MAX] {
+        let mut buf = [0u8; 10];
+        let n = encode_varint64(value, &mut buf);
+        assert_eq!(decode_varint64(&buf[..n]).unwrap(), (value, n));
+    }
+
+    for value in [0, 1, -1, i32::MIN, i32::MAX] {
+        let mut buf = [0u8; 5];
+        let n = encode_varint32(value, &mut buf);
+        assert_eq!(decode_varint32(&buf[..n]).unwrap(), (value, n));
+    }
+}
+
+#[test]
+fn test_uvarint32() {
+    let mut buf = [0u8; 5];
+    assert_eq!(encode_uvarint32(u32::MAX, &mut buf), 5);
+    assert_eq!(buf, [0xff, 0xff, 0xff, 0xff, 0x0f]);
+    assert_eq!(decode_uvarint32(&buf).unwrap(), (u32::MAX, 5));
+
+    assert!(matches!(
+        decode_uvarint32(&[0x80; 5]),
+        Err(VarintError::MalformedVarint)
+    ));
+}
+
+#[test]
+fn test_decode_errors() {
+    assert!(matches!(
+        decode_uvarint64(&[]),
+        Err(VarintError::UnexpectedEof)
+    ));
+    assert!(matches!(
+        decode_uvarint64(&[0xac]),
+        Err(VarintError::UnexpectedEof)
+    ));
+    assert!(matches!(
+        decode_uvarint64(&[0xff; 10]),
+        Err(VarintError::MalformedVarint)
+    ));
+    assert_eq!(decode_uvarint64(&[0xac, 0x02]).unwrap(), (300, 2));
+}
+
+#[test]
+fn test_encoded_len() {
+    use dungers_varint::{encoded_len_i32, encoded_len_i64, encoded_len_u32, encoded_len_u64};
+
+    for shift in 0..64 {
+        for value in [1u64 << shift, (1u64 << shift) - 1] {
+            let mut buf = [0u8; 10];
+            assert_eq!(encoded_len_u64(value), encode_uvarint64(value, &mut buf));
+        }
+    }
+    assert_eq!(encoded_len_u32(0), 1);
+    assert_eq!(encoded_len_u32(u32::MAX), 5);
+
+    for value in [0, -1, 63, -64, 64, -65, i64::MIN, i64::MAX] {
+        let mut buf = [0u8; 10];
+        assert_eq!(encoded_len_i64(value), encode_varint64(value, &mut buf));
+    }
+    assert_eq!(encoded_len_i32(-64), 1);
+    assert_eq!(encoded_len_i32(i32::MIN), 5);
+}
+
+#[test]
+fn test_encode_to_array() {
+    use dungers_varint::{encode_to_array, encoded_len_u64};
+
+    const ENCODED: ([u8; 10], usize) = encode_to_array(u64::MAX);
+    const LEN: usize = encoded_len_u64(u64::MAX);
+    assert_eq!(ENCODED.1, LEN);
+    assert_eq!(decode_uvarint64(&ENCODED.0).unwrap(), (u64::MAX, LEN));
+}
+
+#[test]
+fn test_decode_strict() {
+    use dungers_varint::{
+        decode_uvarint32_strict, decode_uvarint64_strict, decode_varint64_strict,
+    };
+
+    assert_eq!(decode_uvarint64_strict(&[0x00]).unwrap(), (0, 1));
+    assert_eq!(
+        decode_uvarint64_strict(&[0xac, 0x02, 0x00]).unwrap(),
+        (300, 2)
+    );
+    assert_eq!(decode_varint64_strict(&[0x01]).unwrap(), (-1, 1));
+
+    // overlong encodings of 0 and 1.
+    for buf in [&[0x80, 0x00][..], &[0x81, 0x80, 0x00]] {
+        assert_eq!(decode_uvarint64(buf).unwrap().1, buf.len());
+        assert!(matches!(
+            decode_uvarint64_strict(buf),
+            Err(VarintError::NonCanonical)
+        ));
+    }
+
+    let mut max = [0xff; 10];
+    max[9] = 0x01;
+    assert_eq!(decode_uvarint64_strict(&max).unwrap(), (u64::MAX, 10));
+    max[9] = 0x7f;
+    assert_eq!(decode_uvarint64(&max).unwrap(), (u64::MAX, 10));
+    assert!(matches!(
+        decode_uvarint64_strict(&max),
+        Err(VarintError::Overflow)
+    ));
+
+    let buf = [0xff, 0xff, 0xff, 0xff, 0x10];
+    assert!(matches!(
+        decode_uvarint32_strict(&buf),
+        Err(VarintError::Overflow)
+    ));
+}
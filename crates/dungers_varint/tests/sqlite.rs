@@ -0,0 +1,75 @@
+use dungers_varint::{VarintError, sqlite};
+
+const VECTORS: [(u64, &[u8]); 7] = [
+    (0, &[0x00]),
+    (127, &[0x7f]),
+    (128, &[0x81, 0x00]),
+    (240, &[0x81, 0x70]),
+    (
+        (1 << 56) - 1,
+        &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f],
+    ),
+    (
+        1 << 56,
+        &[0x80, 0xc0, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x00],
+    ),
+    (
+        u64::MAX,
+        &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+    ),
+];
+
+#[test]
+fn test_sqlite_known_vectors() {
+    for (value, want) in VECTORS {
+        let mut buf = [0u8; sqlite::MAX_LEN];
+        let n = sqlite::encode(value, &mut buf).unwrap();
+        assert_eq!(&buf[..n], want);
+        assert_eq!(sqlite::encoded_len(value), n);
+        assert_eq!(sqlite::decode(want).unwrap(), (value, n));
+    }
+}
+
+#[test]
+fn test_sqlite_round_trip() {
+    let values = (0..64)
+        .flat_map(|shift| [1u64 << shift, (1u64 << shift) - 1])
+        .chain((-500i64..500).map(|i| (i as u64).wrapping_mul(0x0012_3457_89ab_cdef)));
+    for value in values {
+        let mut buf = [0u8; sqlite::MAX_LEN];
+        let n = sqlite::encode(value, &mut buf).unwrap();
+        assert_eq!(sqlite::encoded_len(value), n);
+        assert_eq!(sqlite::decode(&buf[..n]).unwrap(), (value, n));
+    }
+}
+
+#[test]
+fn test_sqlite_errors() {
+    assert!(matches!(
+        sqlite::decode(&[]),
+        Err(VarintError::UnexpectedEof)
+    ));
+    assert!(matches!(
+        sqlite::decode(&[0xff; 8]),
+        Err(VarintError::UnexpectedEof)
+    ));
+    assert!(matches!(
+        sqlite::encode(u64::MAX, &mut [0u8; 8]),
+        Err(VarintError::BufferTooSmall)
+    ));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_sqlite_io() {
+    let mut buf = Vec::new();
+    for (value, _) in VECTORS {
+        sqlite::write(&mut buf, value).unwrap();
+    }
+
+    let mut rdr = &buf[..];
+    for (value, encoded) in VECTORS {
+        assert_eq!(sqlite::read(&mut rdr).unwrap(), (value, encoded.len()));
+    }
+    assert!(rdr.is_empty());
+}
@@ -0,0 +1,103 @@
+mod common;
+
+use dungers_varint::{VarintError, streamvbyte};
+
+use common::values;
+
+#[test]
+fn test_streamvbyte_known_vectors() {
+    let mut buf = [0u8; streamvbyte::max_encoded_len_u32s(5)];
+    let n = streamvbyte::encode_u32s(&[1, 1 << 8, 1 << 16, 1 << 24, 0], &mut buf).unwrap();
+    assert_eq!(
+        &buf[..n],
+        &[0b1110_0100, 0b0000_0000, 1, 0, 1, 0, 0, 1, 0, 0, 0, 1, 0]
+    );
+
+    let mut buf = [0u8; streamvbyte::max_encoded_len_u64s(3)];
+    let n = streamvbyte::encode_u64s(&[u64::MAX, 0x1234, 5], &mut buf).unwrap();
+    assert_eq!(
+        &buf[..n],
+        &[
+            0x17, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x34, 0x12, 5
+        ]
+    );
+}
+
+#[test]
+fn test_streamvbyte_round_trip() {
+    for len in [0, 1, 3, 4, 5, 17, 1000] {
+        let values = values(len);
+        let mut buf = vec![0u8; streamvbyte::max_encoded_len_u64s(len)];
+        let n = streamvbyte::encode_u64s(&values, &mut buf).unwrap();
+
+        let mut out = vec![0u64; len];
+        assert_eq!(streamvbyte::decode_u64s(&buf[..n], &mut out).unwrap(), n);
+        assert_eq!(out, values);
+
+        let mut scalar = vec![0u64; len];
+        let scalar_n = streamvbyte::scalar::decode_u64s(&buf[..n], &mut scalar).unwrap();
+        assert_eq!((scalar_n, scalar), (n, values.clone()));
+
+        let values: Vec<u32> = values.into_iter().map(|x| x as u32).collect();
+        let mut buf = vec![0u8; streamvbyte::max_encoded_len_u32s(len)];
+        let n = streamvbyte::encode_u32s(&values, &mut buf).unwrap();
+
+        // trailing bytes must not be picked up.
+        let mut out = vec![0u32; len];
+        assert_eq!(streamvbyte::decode_u32s(&buf, &mut out).unwrap(), n);
+        assert_eq!(out, values);
+
+        let mut scalar = vec![0u32; len];
+        let scalar_n = streamvbyte::scalar::decode_u32s(&buf, &mut scalar).unwrap();
+        assert_eq!((scalar_n, scalar), (n, values));
+    }
+}
+
+#[test]
+fn test_streamvbyte_errors() {
+    let values: Vec<u32> = values(64).into_iter().map(|x| x as u32).collect();
+    let mut buf = vec![0u8; streamvbyte::max_encoded_len_u32s(values.len())];
+    let n = streamvbyte::encode_u32s(&values, &mut buf).unwrap();
+    assert!(matches!(
+        streamvbyte::encode_u32s(&values, &mut buf[..n - 1]),
+        Err(VarintError::BufferTooSmall)
+    ));
+
+    let mut out = vec![0u32; values.len()];
+    assert!(matches!(
+        streamvbyte::decode_u32s(&buf[..n - 1], &mut out),
+        Err(VarintError::UnexpectedEof)
+    ));
+    assert!(matches!(
+        streamvbyte::decode_u32s(&buf[..3], &mut out),
+        Err(VarintError::UnexpectedEof)
+    ));
+}
+
+#[test]
+fn test_streamvbyte_malformed_control() {
+    // 4-bit codes can claim up to 16 bytes for a `u64`. enough data follows for the fast path to
+    // kick in.
+    let buf = [0xff; 40];
+    let mut out = [0u64; 2];
+    assert!(matches!(
+        streamvbyte::decode_u64s(&buf, &mut out),
+        Err(VarintError::MalformedVarint)
+    ));
+    assert!(matches!(
+        streamvbyte::scalar::decode_u64s(&buf, &mut out),
+        Err(VarintError::MalformedVarint)
+    ));
+
+    // only the second value is malformed.
+    let mut buf = [0u8; 40];
+    buf[0] = 0x97;
+    assert!(matches!(
+        streamvbyte::decode_u64s(&buf, &mut out),
+        Err(VarintError::MalformedVarint)
+    ));
+    assert!(matches!(
+        streamvbyte::scalar::decode_u64s(&buf, &mut out),
+        Err(VarintError::MalformedVarint)
+    ));
+}
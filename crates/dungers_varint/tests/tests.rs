@@ -68,3 +68,36 @@ pub fn test_varint64() {
         assert_eq!(got, *want);
     }
 }
+
+#[test]
+pub fn test_read_strict() {
+    use dungers_varint::{VarintError, read_uvarint32, read_uvarint32_strict};
+
+    let buf = [0xff, 0xff, 0xff, 0xff, 0x1f];
+    assert_eq!(read_uvarint32(&mut &buf[..]).unwrap(), (u32::MAX, 5));
+    assert!(matches!(
+        read_uvarint32_strict(&mut &buf[..]),
+        Err(VarintError::Overflow)
+    ));
+
+    let buf = [0x80, 0x80, 0x00];
+    assert!(matches!(
+        read_uvarint32_strict(&mut &buf[..]),
+        Err(VarintError::NonCanonical)
+    ));
+}
+
+#[test]
+pub fn test_varint32() {
+    use dungers_varint::{read_uvarint32, read_varint32, write_uvarint32, write_varint32};
+
+    let mut buf = Vec::new();
+    assert_eq!(write_uvarint32(&mut buf, u32::MAX).unwrap(), 5);
+    assert_eq!(write_varint32(&mut buf, i32::MIN).unwrap(), 5);
+    assert_eq!(write_varint32(&mut buf, -1).unwrap(), 1);
+
+    let mut rdr = &buf[..];
+    assert_eq!(read_uvarint32(&mut rdr).unwrap(), (u32::MAX, 5));
+    assert_eq!(read_varint32(&mut rdr).unwrap(), (i32::MIN, 5));
+    assert_eq!(read_varint32(&mut rdr).unwrap(), (-1, 1));
+}
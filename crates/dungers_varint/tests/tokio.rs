@@ -0,0 +1,175 @@
+use std::{
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use dungers_varint::{
+    VarintError,
+    tokio::{FrameReader, FrameWriter, VarintReader, write_uvarint64, write_varint64},
+};
+use futures::FutureExt;
+use tokio::io::{AsyncWriteExt, ReadBuf, duplex};
+
+#[tokio::test]
+async fn test_varint_round_trip() {
+    let (mut client, server) = duplex(64);
+    let values = [0u64, 1, 127, 128, 300, u64::MAX];
+    for value in values {
+        write_uvarint64(&mut client, value).await.unwrap();
+    }
+    write_varint64(&mut client, -2).await.unwrap();
+    drop(client);
+
+    let mut rdr = VarintReader::new(server);
+    for value in values {
+        assert_eq!(rdr.read_uvarint64().await.unwrap().0, value);
+    }
+    assert_eq!(rdr.read_varint64().await.unwrap(), (-2, 1));
+    assert!(matches!(
+        rdr.read_uvarint64().await,
+        Err(VarintError::UnexpectedEof)
+    ));
+}
+
+#[tokio::test]
+async fn test_varint_cancel_safe() {
+    let (mut client, server) = duplex(64);
+    let mut rdr = VarintReader::new(server);
+
+    // the first byte of 300 arrives, the read is then dropped while waiting for the second, like
+    // in a `select!` whose other branch won.
+    client.write_all(&[0xac]).await.unwrap();
+    assert!(rdr.read_uvarint32().now_or_never().is_none());
+
+    client.write_all(&[0x02]).await.unwrap();
+    assert_eq!(rdr.read_uvarint32().await.unwrap(), (300, 2));
+}
+
+#[tokio::test]
+async fn test_frames() {
+    let (client, server) = duplex(16);
+    let frames = [b"hello".to_vec(), Vec::new(), vec![0xaa; 300]];
+
+    let writer = {
+        let frames = frames.clone();
+        tokio::spawn(async move {
+            let mut fw = FrameWriter::new(client);
+            for frame in &frames {
+                fw.write_frame(frame).await.unwrap();
+            }
+            fw.flush().await.unwrap();
+        })
+    };
+
+    let mut fr = FrameReader::new(server);
+    for frame in &frames {
+        assert_eq!(fr.read_frame().await.unwrap().unwrap(), frame);
+    }
+    writer.await.unwrap();
+    assert!(fr.read_frame().await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_frames_cancel_safe() {
+    let (mut client, server) = duplex(64);
+    let mut fr = FrameReader::new(server);
+
+    client.write_all(&[0x05, b'h', b'e']).await.unwrap();
+    assert!(fr.read_frame().now_or_never().is_none());
+    client.write_all(b"llo").await.unwrap();
+    assert_eq!(fr.read_frame().await.unwrap().unwrap(), b"hello");
+
+    // the duplex buffer fills up part way through the frame.
+    let (client, mut server) = duplex(4);
+    let mut fw = FrameWriter::new(client);
+    assert!(fw.write_frame(b"hello").now_or_never().is_none());
+    assert_eq!(fw.pending(), 2);
+
+    let mut buf = [0u8; 6];
+    tokio::io::AsyncReadExt::read_exact(&mut server, &mut buf[..4])
+        .await
+        .unwrap();
+    fw.flush().await.unwrap();
+    tokio::io::AsyncReadExt::read_exact(&mut server, &mut buf[4..])
+        .await
+        .unwrap();
+    assert_eq!(&buf, b"\x05hello");
+}
+
+/// fails every other read and write with `Interrupted`, like a syscall hit by a signal.
+struct Interrupting {
+    buf: Vec<u8>,
+    pos: usize,
+    interrupt: bool,
+}
+
+impl Interrupting {
+    fn new(buf: &[u8]) -> Self {
+        Self {
+            buf: buf.to_vec(),
+            pos: 0,
+            interrupt: false,
+        }
+    }
+
+    fn interrupt(&mut self) -> bool {
+        self.interrupt = !self.interrupt;
+        self.interrupt
+    }
+}
+
+impl tokio::io::AsyncRead for Interrupting {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        if self.interrupt() {
+            return Poll::Ready(Err(io::ErrorKind::Interrupted.into()));
+        }
+        if let Some(&byte) = self.buf.get(self.pos) {
+            buf.put_slice(&[byte]);
+            self.pos += 1;
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl tokio::io::AsyncWrite for Interrupting {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        if self.interrupt() {
+            return Poll::Ready(Err(io::ErrorKind::Interrupted.into()));
+        }
+        self.buf.push(buf[0]);
+        Poll::Ready(Ok(1))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[tokio::test]
+async fn test_interrupted() {
+    let mut rdr = VarintReader::new(Interrupting::new(&[0xac, 0x02]));
+    assert_eq!(rdr.read_uvarint32().await.unwrap(), (300, 2));
+
+    let mut fr = FrameReader::new(Interrupting::new(b"\x05hello"));
+    assert_eq!(fr.read_frame().await.unwrap(), Some(&b"hello"[..]));
+
+    let mut w = Interrupting::new(&[]);
+    assert_eq!(write_uvarint64(&mut w, 300).await.unwrap(), 2);
+    let mut fw = FrameWriter::new(w);
+    fw.write_frame(b"hello").await.unwrap();
+    fw.flush().await.unwrap();
+    assert_eq!(fw.into_inner().buf, b"\xac\x02\x05hello");
+}
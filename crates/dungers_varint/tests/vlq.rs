@@ -0,0 +1,117 @@
+use dungers_varint::{VarintError, vlq};
+
+// NOTE: vectors are from the standard midi file spec.
+const VECTORS: [(u64, &[u8]); 12] = [
+    (0x0000_0000, &[0x00]),
+    (0x0000_0040, &[0x40]),
+    (0x0000_007f, &[0x7f]),
+    (0x0000_0080, &[0x81, 0x00]),
+    (0x0000_2000, &[0xc0, 0x00]),
+    (0x0000_3fff, &[0xff, 0x7f]),
+    (0x0000_4000, &[0x81, 0x80, 0x00]),
+    (0x0010_0000, &[0xc0, 0x80, 0x00]),
+    (0x001f_ffff, &[0xff, 0xff, 0x7f]),
+    (0x0020_0000, &[0x81, 0x80, 0x80, 0x00]),
+    (0x0800_0000, &[0xc0, 0x80, 0x80, 0x00]),
+    (0x0fff_ffff, &[0xff, 0xff, 0xff, 0x7f]),
+];
+
+// NOTE: vectors are worked out by hand from git's `write_no_reuse_object` and
+// `get_delta_base`.
+const OFFSET_VECTORS: [(u64, &[u8]); 6] = [
+    (0, &[0x00]),
+    (127, &[0x7f]),
+    (128, &[0x80, 0x00]),
+    (16511, &[0xff, 0x7f]),
+    (16512, &[0x80, 0x80, 0x00]),
+    (2_113_663, &[0xff, 0xff, 0x7f]),
+];
+
+fn values() -> impl Iterator<Item = u64> {
+    (0..64)
+        .flat_map(|shift| [1u64 << shift, (1u64 << shift) - 1])
+        .chain([u64::MAX])
+        .chain((-500i64..500).map(|i| (i as u64).wrapping_mul(0x0012_3457_89ab_cdef)))
+}
+
+#[test]
+fn test_vlq_known_vectors() {
+    for (value, want) in VECTORS {
+        let mut buf = [0u8; vlq::MAX_LEN];
+        let n = vlq::encode(value, &mut buf).unwrap();
+        assert_eq!(&buf[..n], want);
+        assert_eq!(vlq::encoded_len(value), n);
+        assert_eq!(vlq::decode(want).unwrap(), (value, n));
+    }
+}
+
+#[test]
+fn test_vlq_round_trip() {
+    for value in values() {
+        let mut buf = [0u8; vlq::MAX_LEN];
+        let n = vlq::encode(value, &mut buf).unwrap();
+        assert_eq!(vlq::encoded_len(value), n);
+        assert_eq!(vlq::decode(&buf[..n]).unwrap(), (value, n));
+
+        let n = vlq::encode_offset(value, &mut buf).unwrap();
+        assert_eq!(vlq::offset_encoded_len(value), n);
+        assert_eq!(vlq::decode_offset(&buf[..n]).unwrap(), (value, n));
+    }
+}
+
+#[test]
+fn test_vlq_offset_known_vectors() {
+    for (value, want) in OFFSET_VECTORS {
+        let mut buf = [0u8; vlq::MAX_LEN];
+        let n = vlq::encode_offset(value, &mut buf).unwrap();
+        assert_eq!(&buf[..n], want);
+        assert_eq!(vlq::decode_offset(want).unwrap(), (value, n));
+    }
+}
+
+#[test]
+fn test_vlq_errors() {
+    assert!(matches!(vlq::decode(&[]), Err(VarintError::UnexpectedEof)));
+    assert!(matches!(
+        vlq::decode(&[0x81, 0x80]),
+        Err(VarintError::UnexpectedEof)
+    ));
+    // 2^64 does not fit.
+    let mut too_big = [0x80; 10];
+    too_big[0] = 0x82;
+    too_big[9] = 0x00;
+    assert!(matches!(vlq::decode(&too_big), Err(VarintError::Overflow)));
+    assert!(matches!(
+        vlq::decode(&[0x80; 10]),
+        Err(VarintError::MalformedVarint)
+    ));
+    assert!(matches!(
+        vlq::decode_offset(&[0xff; 10]),
+        Err(VarintError::Overflow)
+    ));
+    assert!(matches!(
+        vlq::encode(0x80, &mut [0u8; 1]),
+        Err(VarintError::BufferTooSmall)
+    ));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_vlq_io() {
+    let mut buf = Vec::new();
+    for (value, _) in VECTORS {
+        vlq::write(&mut buf, value).unwrap();
+    }
+    for (value, _) in OFFSET_VECTORS {
+        vlq::write_offset(&mut buf, value).unwrap();
+    }
+
+    let mut rdr = &buf[..];
+    for (value, encoded) in VECTORS {
+        assert_eq!(vlq::read(&mut rdr).unwrap(), (value, encoded.len()));
+    }
+    for (value, encoded) in OFFSET_VECTORS {
+        assert_eq!(vlq::read_offset(&mut rdr).unwrap(), (value, encoded.len()));
+    }
+    assert!(rdr.is_empty());
+}
@@ -0,0 +1,197 @@
+use dungers_varint::VarintError;
+use dungers_varint::wire::{Tag, WireReader, WireType, WireWriter};
+
+// examples from https://protobuf.dev/programming-guides/encoding/
+const SIMPLE: [u8; 3] = [0x08, 0x96, 0x01];
+const STRING: [u8; 9] = [0x12, 0x07, b't', b'e', b's', b't', b'i', b'n', b'g'];
+const PACKED: [u8; 8] = [0x22, 0x06, 0x03, 0x8e, 0x02, 0x9e, 0xa7, 0x05];
+
+#[test]
+fn test_read_known_messages() {
+    let mut r = WireReader::new(&SIMPLE);
+    assert_eq!(r.read_tag().unwrap(), Some(Tag::new(1, WireType::Varint)));
+    assert_eq!(r.read_varint::<u32>().unwrap(), 150);
+    assert_eq!(r.read_tag().unwrap(), None);
+
+    let mut r = WireReader::new(&STRING);
+    let tag = r.read_tag().unwrap().unwrap();
+    assert_eq!(tag, Tag::new(2, WireType::LengthDelimited));
+    assert_eq!(r.read_length_delimited().unwrap(), b"testing");
+
+    let mut r = WireReader::new(&PACKED);
+    assert_eq!(r.read_tag().unwrap().unwrap().field_number, 4);
+    let values: Result<Vec<u32>, _> = r.read_packed_varints().unwrap().collect();
+    assert_eq!(values.unwrap(), [3, 270, 86942]);
+    assert!(r.is_empty());
+}
+
+#[test]
+fn test_skip_field() {
+    let mut buf = [0u8; 128];
+    let mut w = WireWriter::new(&mut buf);
+    w.write_tag(Tag::new(1, WireType::Fixed32)).unwrap();
+    w.write_fixed32(7).unwrap();
+    w.write_tag(Tag::new(2, WireType::StartGroup)).unwrap();
+    w.write_tag(Tag::new(3, WireType::StartGroup)).unwrap();
+    w.write_tag(Tag::new(4, WireType::Varint)).unwrap();
+    w.write_varint(u64::MAX).unwrap();
+    w.write_tag(Tag::new(3, WireType::EndGroup)).unwrap();
+    w.write_tag(Tag::new(2, WireType::EndGroup)).unwrap();
+    w.write_tag(Tag::new(5, WireType::LengthDelimited)).unwrap();
+    w.write_length_delimited(b"skipped").unwrap();
+    w.write_tag(Tag::new(6, WireType::Fixed64)).unwrap();
+    w.write_fixed64(u64::MAX).unwrap();
+    w.write_tag(Tag::new(7, WireType::Varint)).unwrap();
+    w.write_varint(-42i32).unwrap();
+    let len = w.position();
+
+    let mut r = WireReader::new(&buf[..len]);
+    while let Some(tag) = r.read_tag().unwrap() {
+        if tag.field_number == 7 {
+            assert_eq!(r.read_varint::<i32>().unwrap(), -42);
+        } else {
+            r.skip_field(tag).unwrap();
+        }
+    }
+}
+
+#[test]
+fn test_writer_round_trip() {
+    let mut buf = [0u8; 64];
+    let mut w = WireWriter::new(&mut buf);
+    w.write_tag(Tag::new(4, WireType::LengthDelimited)).unwrap();
+    w.write_packed_varints(&[3u32, 270, 86942]).unwrap();
+    assert_eq!(w.written(), PACKED);
+
+    let mut buf = [0u8; 64];
+    let mut w = WireWriter::new(&mut buf);
+    w.write_tag(Tag::new(3, WireType::LengthDelimited)).unwrap();
+    w.write_message(|w| {
+        w.write_tag(Tag::new(1, WireType::Varint))?;
+        w.write_varint(150u32)
+    })
+    .unwrap();
+    w.write_tag(Tag::new(8, WireType::LengthDelimited)).unwrap();
+    w.write_packed_fixed32(&[1, u32::MAX]).unwrap();
+    // the embedded message example from the encoding guide.
+    assert_eq!(w.written()[..5], [0x1a, 0x03, 0x08, 0x96, 0x01]);
+
+    let mut r = WireReader::new(w.written());
+    r.read_tag().unwrap();
+    let mut inner = r.read_message().unwrap();
+    assert_eq!(inner.read_tag().unwrap().unwrap().field_number, 1);
+    assert_eq!(inner.read_varint::<u32>().unwrap(), 150);
+    r.read_tag().unwrap();
+    assert!(r.read_packed_fixed32().unwrap().eq([1, u32::MAX]));
+}
+
+#[test]
+fn test_write_message_exact_fit() {
+    let message = |w: &mut WireWriter<'_>| {
+        w.write_tag(Tag::new(1, WireType::Varint))?;
+        w.write_varint(150u32)
+    };
+
+    // tag, 1-byte length prefix and the 3-byte body, without any slack.
+    let mut buf = [0u8; 5];
+    let mut w = WireWriter::new(&mut buf);
+    w.write_tag(Tag::new(3, WireType::LengthDelimited)).unwrap();
+    w.write_message(message).unwrap();
+    assert_eq!(w.written(), [0x1a, 0x03, 0x08, 0x96, 0x01]);
+
+    let mut buf = [0u8; 4];
+    let mut w = WireWriter::new(&mut buf);
+    w.write_tag(Tag::new(3, WireType::LengthDelimited)).unwrap();
+    assert!(matches!(
+        w.write_message(message),
+        Err(VarintError::BufferTooSmall)
+    ));
+
+    // a 2-byte length prefix around a 199-byte body.
+    let nested = |w: &mut WireWriter<'_>| w.write_length_delimited(&[7; 197]);
+    let mut buf = [0u8; 201];
+    let mut w = WireWriter::new(&mut buf);
+    w.write_message(nested).unwrap();
+    assert_eq!(w.written()[..4], [0xc7, 0x01, 0xc5, 0x01]);
+    assert!(matches!(
+        WireWriter::new(&mut buf[..200]).write_message(nested),
+        Err(VarintError::BufferTooSmall)
+    ));
+}
+
+#[test]
+fn test_errors() {
+    // wire type 7 does not exist; field number 0 is reserved.
+    assert!(matches!(
+        WireReader::new(&[0x0f]).read_tag(),
+        Err(VarintError::InvalidTag)
+    ));
+    assert!(matches!(
+        WireReader::new(&[0x00]).read_tag(),
+        Err(VarintError::InvalidTag)
+    ));
+    // length runs past the input.
+    assert!(matches!(
+        WireReader::new(&STRING[1..8]).read_length_delimited(),
+        Err(VarintError::UnexpectedEof)
+    ));
+
+    let mut buf = [0u8; 2];
+    let mut w = WireWriter::new(&mut buf);
+    assert!(matches!(
+        w.write_fixed32(1),
+        Err(VarintError::BufferTooSmall)
+    ));
+}
+
+#[test]
+fn test_oversized_varints() {
+    // a length of 2^32 + 5 must not be truncated to 5.
+    let mut buf = [0u8; 16];
+    let mut w = WireWriter::new(&mut buf);
+    w.write_tag(Tag::new(2, WireType::LengthDelimited)).unwrap();
+    w.write_varint((1u64 << 32) | 5).unwrap();
+    w.write_fixed64(0).unwrap();
+    let len = w.position();
+
+    let mut r = WireReader::new(&buf[..len]);
+    let tag = r.read_tag().unwrap().unwrap();
+    assert!(matches!(
+        r.clone().read_length_delimited(),
+        Err(VarintError::Overflow)
+    ));
+    assert!(matches!(r.skip_field(tag), Err(VarintError::Overflow)));
+
+    // same for tags.
+    let mut buf = [0u8; 16];
+    let mut w = WireWriter::new(&mut buf);
+    w.write_varint((1u64 << 32) | 0x08).unwrap();
+    let len = w.position();
+    assert!(matches!(
+        WireReader::new(&buf[..len]).read_tag(),
+        Err(VarintError::InvalidTag)
+    ));
+}
+
+#[test]
+fn test_field_number_range() {
+    let tag = Tag::new(Tag::MAX_FIELD_NUMBER, WireType::Varint);
+    assert_eq!(Tag::from_u32(tag.to_u32().unwrap()).unwrap(), tag);
+
+    let tag = Tag {
+        field_number: Tag::MAX_FIELD_NUMBER + 1,
+        wire_type: WireType::Varint,
+    };
+    assert!(matches!(tag.to_u32(), Err(VarintError::InvalidTag)));
+    let mut buf = [0u8; 8];
+    assert!(matches!(
+        WireWriter::new(&mut buf).write_tag(tag),
+        Err(VarintError::InvalidTag)
+    ));
+}
+
+#[test]
+#[should_panic = "field number out of range"]
+fn test_field_number_too_large() {
+    let _ = Tag::new(Tag::MAX_FIELD_NUMBER + 1, WireType::Varint);
+}
@@ -0,0 +1,59 @@
+/// errors from any of the enabled sub-crates.
+///
+/// cases that several sub-crates share (varint and protobuf tag errors, buffers that are too small
+/// and io errors) are folded into a single variant, everything else is wrapped as is.
+#[derive(thiserror::Error, Debug)]
+#[non_exhaustive]
+pub enum Error {
+    #[error("malformed varint")]
+    MalformedVarint,
+    #[error("non-canonical varint: encoding is longer than necessary")]
+    NonCanonicalVarint,
+    #[error("varint overflows the target type")]
+    VarintOverflow,
+    #[error("invalid protobuf tag")]
+    InvalidTag,
+    #[error("buffer too small")]
+    BufferTooSmall,
+    #[cfg(feature = "std")]
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[cfg(feature = "bitbuf")]
+    #[error(transparent)]
+    Bit(dungers_bitbuf::BitError),
+    #[cfg(feature = "varint")]
+    #[error(transparent)]
+    Varint(dungers_varint::VarintError),
+}
+
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+
+#[cfg(feature = "bitbuf")]
+impl From<dungers_bitbuf::BitError> for Error {
+    fn from(err: dungers_bitbuf::BitError) -> Self {
+        match err {
+            dungers_bitbuf::BitError::MalformedVarint => Self::MalformedVarint,
+            dungers_bitbuf::BitError::NonCanonicalVarint => Self::NonCanonicalVarint,
+            dungers_bitbuf::BitError::VarintOverflow => Self::VarintOverflow,
+            dungers_bitbuf::BitError::InvalidTag => Self::InvalidTag,
+            dungers_bitbuf::BitError::BufferTooSmall => Self::BufferTooSmall,
+            err => Self::Bit(err),
+        }
+    }
+}
+
+#[cfg(feature = "varint")]
+impl From<dungers_varint::VarintError> for Error {
+    fn from(err: dungers_varint::VarintError) -> Self {
+        match err {
+            dungers_varint::VarintError::MalformedVarint => Self::MalformedVarint,
+            dungers_varint::VarintError::NonCanonical => Self::NonCanonicalVarint,
+            dungers_varint::VarintError::Overflow => Self::VarintOverflow,
+            dungers_varint::VarintError::InvalidTag => Self::InvalidTag,
+            dungers_varint::VarintError::BufferTooSmall => Self::BufferTooSmall,
+            #[cfg(feature = "std")]
+            dungers_varint::VarintError::IoError(err) => Self::Io(err),
+            err => Self::Varint(err),
+        }
+    }
+}
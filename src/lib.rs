@@ -1,3 +1,16 @@
+#![no_std]
+#![deny(clippy::all)]
+#![deny(unreachable_pub)]
+#![deny(clippy::pedantic)]
+#![deny(clippy::std_instead_of_core)]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+mod error;
+
+pub use error::{Error, Result};
+
 #[cfg(feature = "bitbuf")]
 pub use dungers_bitbuf as bitbuf;
 #[cfg(feature = "charsor")]
@@ -0,0 +1,44 @@
+use dungers::bitbuf::{BitError, BitRead, BitReader};
+use dungers::varint::VarintError;
+use dungers::{Error, Result};
+
+fn read_u32(buf: &[u8]) -> Result<u32> {
+    Ok(BitReader::new(buf).read_u32()?)
+}
+
+#[test]
+fn test_from_bit_error() {
+    assert_eq!(read_u32(&[1, 0, 0, 0]).unwrap(), 1);
+    assert!(matches!(
+        read_u32(&[1, 0]),
+        Err(Error::Bit(BitError::Overflow))
+    ));
+}
+
+#[test]
+fn test_malformed_varint_is_shared() {
+    assert!(matches!(
+        Error::from(BitError::MalformedVarint),
+        Error::MalformedVarint
+    ));
+    assert!(matches!(
+        Error::from(VarintError::MalformedVarint),
+        Error::MalformedVarint
+    ));
+    assert!(matches!(
+        Error::from(BitError::BufferTooSmall),
+        Error::BufferTooSmall
+    ));
+    assert!(matches!(
+        Error::from(VarintError::BufferTooSmall),
+        Error::BufferTooSmall
+    ));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_io_error() {
+    let mut buf: &[u8] = &[0x80];
+    let err = Error::from(dungers::varint::read_uvarint64(&mut buf).unwrap_err());
+    assert!(matches!(err, Error::Io(err) if err.kind() == std::io::ErrorKind::UnexpectedEof));
+}